@@ -0,0 +1,187 @@
+//! Compares the flat `TileMap` and `TileSet` storage of `Game` with the former nested vector,
+//! hash set and flag vector layout on 1000x1000 boards
+use board_plugin::components::Coordinates;
+use board_plugin::game::{Game, Outcome};
+use board_plugin::resources::{BombPlacement, Tile, Topology};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+const SIZE: u16 = 1000;
+const BOMBS: u16 = 10_000;
+const SEED: u64 = 42;
+
+/// The former layout: a `Vec<Vec<Tile>>` map, covered tiles in a hash set and flags in a vector
+mod nested {
+    use board_plugin::components::Coordinates;
+    use board_plugin::resources::Tile;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::{HashSet, VecDeque};
+
+    const SQUARE_COORDINATES: [(i8, i8); 8] =
+        [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+    #[derive(Clone)]
+    pub struct NestedGame {
+        map: Vec<Vec<Tile>>,
+        bomb_count: usize,
+        covered: HashSet<Coordinates>,
+        flagged: Vec<Coordinates>,
+    }
+
+    impl NestedGame {
+        pub fn new(width: u16, height: u16, bomb_count: u16, seed: u64) -> Self {
+            let mut map = vec![vec![Tile::Empty; width as usize]; height as usize];
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let mut remaining_bombs = bomb_count;
+            while remaining_bombs > 0 {
+                let (x, y) = (
+                    rng.gen_range(0..width) as usize,
+                    rng.gen_range(0..height) as usize,
+                );
+                if let Tile::Empty = map[y][x] {
+                    map[y][x] = Tile::Bomb;
+                    remaining_bombs -= 1;
+                }
+            }
+            let mut game = Self {
+                map,
+                bomb_count: bomb_count as usize,
+                covered: (0..height)
+                    .flat_map(|y| (0..width).map(move |x| Coordinates { x, y }))
+                    .collect(),
+                flagged: Vec::new(),
+            };
+            for y in 0..height {
+                for x in 0..width {
+                    let coords = Coordinates { x, y };
+                    if game.is_bomb_at(coords) {
+                        continue;
+                    }
+                    let count = game.neighbors(coords).filter(|c| game.is_bomb_at(*c)).count();
+                    game.map[y as usize][x as usize] = match count {
+                        0 => Tile::Empty,
+                        _ => Tile::BombNeighbor(count as u8),
+                    };
+                }
+            }
+            game
+        }
+
+        fn tile_at(&self, coords: Coordinates) -> Option<&Tile> {
+            self.map.get(coords.y as usize)?.get(coords.x as usize)
+        }
+
+        fn is_bomb_at(&self, coords: Coordinates) -> bool {
+            self.tile_at(coords).is_some_and(|t| t.is_bomb())
+        }
+
+        fn neighbors(&self, coords: Coordinates) -> impl Iterator<Item = Coordinates> {
+            SQUARE_COORDINATES
+                .iter()
+                .filter_map(move |offset| coords.checked_add(*offset))
+        }
+
+        /// Uncovers the region of `coords`, returning whether the board is completed
+        pub fn reveal(&mut self, coords: Coordinates) -> bool {
+            let mut queue = VecDeque::from([coords]);
+            let mut completed = false;
+            while let Some(coords) = queue.pop_front() {
+                if self.flagged.contains(&coords) || !self.covered.remove(&coords) {
+                    continue;
+                }
+                // The completion was checked for every uncovered tile
+                completed = self.covered.len() == self.bomb_count;
+                if self.tile_at(coords) == Some(&Tile::Empty) {
+                    queue.extend(self.neighbors(coords).filter(|c| self.covered.contains(c)));
+                }
+            }
+            completed
+        }
+    }
+}
+
+/// Empty tile opening the largest part of the board, and the safe tiles
+fn tiles(game: &Game) -> (Coordinates, Vec<Coordinates>) {
+    let tile_map = game.tile_map();
+    let safe: Vec<_> = tile_map
+        .iter()
+        .filter(|(_, tile)| !tile.is_bomb())
+        .map(|(c, _)| c)
+        .collect();
+    let empty = tile_map
+        .iter()
+        .find(|(_, tile)| **tile == Tile::Empty)
+        .map(|(c, _)| c)
+        .unwrap();
+    (empty, safe)
+}
+
+fn flat_game() -> Game {
+    Game::new(
+        (SIZE, SIZE),
+        BOMBS,
+        SEED,
+        BombPlacement::OnCreation,
+        None,
+        Topology::Square,
+    )
+}
+
+fn generate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate");
+    group.sample_size(10);
+    group.bench_function("flat", |b| {
+        b.iter(flat_game)
+    });
+    group.bench_function("nested", |b| {
+        b.iter(|| nested::NestedGame::new(SIZE, SIZE, BOMBS, SEED))
+    });
+    group.finish();
+}
+
+fn flood_fill(c: &mut Criterion) {
+    let game = flat_game();
+    let nested = nested::NestedGame::new(SIZE, SIZE, BOMBS, SEED);
+    let (empty, _) = tiles(&game);
+    let mut group = c.benchmark_group("flood_fill");
+    group.sample_size(10);
+    group.bench_function("flat", |b| {
+        b.iter_batched(|| game.clone(), |mut g| g.reveal(empty), BatchSize::LargeInput)
+    });
+    group.bench_function("nested", |b| {
+        b.iter_batched(|| nested.clone(), |mut g| g.reveal(empty), BatchSize::LargeInput)
+    });
+    group.finish();
+}
+
+fn completion(c: &mut Criterion) {
+    let game = flat_game();
+    let nested = nested::NestedGame::new(SIZE, SIZE, BOMBS, SEED);
+    let (_, safe) = tiles(&game);
+    let mut group = c.benchmark_group("completion");
+    group.sample_size(10);
+    group.bench_function("flat", |b| {
+        b.iter_batched(
+            || game.clone(),
+            |mut g| {
+                let won = safe.iter().any(|c| g.reveal(*c).outcome == Some(Outcome::Won));
+                assert!(won);
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("nested", |b| {
+        b.iter_batched(
+            || nested.clone(),
+            |mut g| {
+                let won = safe.iter().any(|c| g.reveal(*c));
+                assert!(won);
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, generate, flood_fill, completion);
+criterion_main!(benches);
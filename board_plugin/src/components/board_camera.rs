@@ -0,0 +1,13 @@
+use bevy::prelude::Component;
+
+/// Board camera component, indicates the 2D camera showing the board. The board plugin pans and
+/// zooms it and picks the tiles under the mouse through it
+#[cfg(feature = "debug")]
+use bevy_inspector_egui::prelude::*;
+#[cfg(feature = "debug")]
+use bevy::prelude::Reflect;
+
+#[cfg_attr(feature = "debug", derive(Reflect, InspectorOptions))]
+#[cfg_attr(feature = "debug", reflect(InspectorOptions))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct BoardCamera;
@@ -7,6 +7,7 @@ use std::ops::{Add, Sub};
 // use my_macro::EasyInspector;
 
 use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::prelude::*;
 #[cfg(feature = "debug")]
@@ -14,7 +15,9 @@ use bevy::prelude::Reflect;
 #[cfg_attr(feature = "debug", derive(Reflect, InspectorOptions))]
 #[cfg_attr(feature = "debug", reflect(InspectorOptions))]
 #[cfg_attr(feature = "debug", inspector(validate = |ability| ability.current_charges <= ability.max_charges))]
-#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+#[derive(
+    Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Component,
+)]
 
 // #[derive(EasyInspector)]
 pub struct Coordinates {
@@ -36,14 +39,13 @@ impl Add for Coordinates {
     }
 }
 
-impl Add<(i8, i8)> for Coordinates {
-    type Output = Self;
-
-    fn add(self, (x, y): (i8, i8)) -> Self::Output {
-        Self {
-            x: ((self.x as i16) + x as i16) as u16,
-            y: ((self.y as i16) + y as i16) as u16,
-        }
+impl Coordinates {
+    /// Adds the `(x, y)` delta, if the coordinates stay positive
+    pub fn checked_add(self, (x, y): (i8, i8)) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(x as i16)?,
+            y: self.y.checked_add_signed(y as i16)?,
+        })
     }
 }
 
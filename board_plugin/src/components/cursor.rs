@@ -0,0 +1,15 @@
+use crate::components::Coordinates;
+use bevy::prelude::Component;
+
+/// Cursor component, indicates the keyboard cursor highlight and its tile
+#[cfg(feature = "debug")]
+use bevy_inspector_egui::prelude::*;
+#[cfg(feature = "debug")]
+use bevy::prelude::Reflect;
+
+#[cfg_attr(feature = "debug", derive(Reflect, InspectorOptions))]
+#[cfg_attr(feature = "debug", reflect(InspectorOptions))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct Cursor {
+    pub coordinates: Coordinates,
+}
@@ -0,0 +1,12 @@
+use bevy::prelude::Component;
+
+/// Game over component, indicates the board cover shown when the game is won or lost
+#[cfg(feature = "debug")]
+use bevy_inspector_egui::prelude::*;
+#[cfg(feature = "debug")]
+use bevy::prelude::Reflect;
+
+#[cfg_attr(feature = "debug", derive(Reflect, InspectorOptions))]
+#[cfg_attr(feature = "debug", reflect(InspectorOptions))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct GameOver;
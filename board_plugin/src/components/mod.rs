@@ -3,9 +3,21 @@ mod coordinates;
 mod bomb;
 mod bomb_neighbor;
 mod uncover;
+mod uncover_delay;
+mod hud;
+mod game_over;
+mod cursor;
+mod board_camera;
+mod tile_chunk;
 
 pub use coordinates::Coordinates;
 
 pub use bomb::*;
 pub use bomb_neighbor::*;
-pub use uncover::*;
\ No newline at end of file
+pub use uncover::*;
+pub use uncover_delay::*;
+pub use hud::*;
+pub use game_over::*;
+pub use cursor::*;
+pub use board_camera::*;
+pub use tile_chunk::*;
\ No newline at end of file
@@ -0,0 +1,23 @@
+use crate::components::Coordinates;
+use bevy::prelude::Component;
+
+/// Tile chunk component, indicates a mesh drawing a block of tiles, see `RenderMode::Chunks`
+#[derive(Debug, Clone, Component)]
+pub struct TileChunk {
+    /// Bottom left tile of the chunk
+    pub origin: Coordinates,
+    /// Chunk size in tiles, smaller on the right and top board edges
+    pub size: (u16, u16),
+    /// Atlas cell of each drawn tile and the drawn tile size, `None` before the first draw
+    pub(crate) drawn: Option<(Vec<u8>, f32)>,
+}
+
+impl TileChunk {
+    pub fn new(origin: Coordinates, size: (u16, u16)) -> Self {
+        Self {
+            origin,
+            size,
+            drawn: None,
+        }
+    }
+}
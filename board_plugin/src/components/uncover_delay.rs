@@ -0,0 +1,6 @@
+use bevy::prelude::{Component, Timer};
+
+/// Uncover delay component, indicates a revealed tile cover waiting for its ring of the reveal
+/// animation, see `BoardOptions::reveal_ring_delay`
+#[derive(Debug, Clone, Component)]
+pub struct UncoverDelay(pub Timer);
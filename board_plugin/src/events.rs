@@ -1,4 +1,7 @@
 use crate::components::Coordinates;
+use crate::resources::{BoardOptions, GameStatus};
+use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Debug, Copy, Clone)]
 pub struct TileTriggerEvent(pub Coordinates);
@@ -10,4 +13,60 @@ pub struct BoardCompletedEvent;
 pub struct BombExplosionEvent;
 
 #[derive(Debug, Copy, Clone)]
-pub struct TileMarkEvent(pub Coordinates);
\ No newline at end of file
+pub struct TileMarkEvent(pub Coordinates);
+
+#[derive(Debug, Copy, Clone)]
+pub struct TileChordEvent(pub Coordinates);
+
+/// Sent with the new status when the `GameStatus` changes, and when a new board is created
+#[derive(Debug, Copy, Clone)]
+pub struct GameStatusChangedEvent(pub GameStatus);
+
+/// Rebuilds the current board with every tile covered again, keeping its bombs
+#[derive(Debug, Copy, Clone)]
+pub struct RestartBoardEvent;
+
+/// Replaces the current board with a new one, generated from the given options, or from
+/// the `BoardOptions` resource with a new seed
+#[derive(Debug, Clone)]
+pub struct NewBoardEvent(pub Option<BoardOptions>);
+
+/// Saves the current game, see `SavedGame`, to the given file
+#[derive(Debug, Clone)]
+pub struct SaveBoardEvent(pub PathBuf);
+
+/// Replaces the current board with the `SavedGame` of the given file
+#[derive(Debug, Clone)]
+pub struct LoadBoardEvent(pub PathBuf);
+
+/// Shows the keyboard cursor on a tile the solver finds safe, or on the least likely bomb
+#[derive(Debug, Copy, Clone)]
+pub struct HintEvent;
+
+/// Undoes the last action, if `BoardOptions::undo` is set
+#[derive(Debug, Copy, Clone)]
+pub struct UndoEvent;
+
+/// Redoes the last undone action, if `BoardOptions::undo` is set
+#[derive(Debug, Copy, Clone)]
+pub struct RedoEvent;
+
+/// Saves the replay of the current board, see `ReplayRecorder`, to the given file
+#[derive(Debug, Clone)]
+pub struct SaveReplayEvent(pub PathBuf);
+
+/// Replaces the current board with the `Replay` of the given file and plays it
+#[derive(Debug, Clone)]
+pub struct PlayReplayEvent(pub PathBuf);
+
+/// Controls the `ReplayPlayback`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ReplayControlEvent {
+    TogglePause,
+    /// Sets the playback speed factor
+    SetSpeed(f32),
+    /// Rebuilds the board as it was at the given time
+    Seek(Duration),
+    /// Stops the playback, giving the board back to the player
+    Stop,
+}
\ No newline at end of file
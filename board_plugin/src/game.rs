@@ -0,0 +1,447 @@
+// game.rs
+use crate::components::Coordinates;
+use crate::resources::{BombPlacement, NoGuess, NoGuessFallback, Tile, TileMap, TileSet, Topology};
+use crate::solver;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Outcome of a finished game
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Outcome {
+    /// Every safe tile was uncovered
+    Won,
+    /// A bomb was uncovered
+    Lost,
+}
+
+/// Result of a game action
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Reveal {
+    /// Newly uncovered tiles, in uncovering order
+    pub revealed: Vec<Coordinates>,
+    /// Set if the action ended the game
+    pub outcome: Option<Outcome>,
+}
+
+/// Tiles changed by an undoable action, see `Game::undo`
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Step {
+    /// Uncovered tiles, in uncovering order
+    pub revealed: Vec<Coordinates>,
+    /// Tiles whose flag was toggled, uncovering a tile removes its flag
+    pub flags: Vec<Coordinates>,
+    /// Outcome before the action
+    pub before: Option<Outcome>,
+    /// Outcome after the action
+    pub after: Option<Outcome>,
+}
+
+/// Bomb placement waiting for the first uncovered tile
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+struct PendingBombs {
+    bomb_count: u16,
+    /// Keep the neighbors of the first tile free of bombs
+    safe_square: bool,
+}
+
+/// Minesweeper rules over a `TileMap`, without any rendering
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Game {
+    tile_map: TileMap,
+    seed: u64,
+    pending_bombs: Option<PendingBombs>,
+    no_guess: Option<NoGuess>,
+    /// Tile to uncover first on no guess boards
+    opening: Option<Coordinates>,
+    covered: TileSet,
+    flagged: TileSet,
+    outcome: Option<Outcome>,
+    /// Are the actions recorded to be undone, see `BoardOptions::undo`
+    #[serde(default)]
+    undo: bool,
+    /// Undoable actions, the last one on top
+    #[serde(default)]
+    history: Vec<Step>,
+    /// Undone actions, the last undone one on top
+    #[serde(default)]
+    undone: Vec<Step>,
+}
+
+impl Game {
+    /// Generates a new game of `topology` tiles, placing the bombs according to `placement`.
+    ///
+    /// See `BoardOptions::no_guess` for the opening of no guess boards
+    pub fn new(
+        (width, height): (u16, u16),
+        bomb_count: u16,
+        seed: u64,
+        placement: BombPlacement,
+        no_guess: Option<NoGuess>,
+        topology: Topology,
+    ) -> Self {
+        let tile_map = TileMap::with_topology(width, height, topology);
+        let mut game = Self::from_tile_map(tile_map, seed);
+        game.no_guess = no_guess;
+        match placement {
+            BombPlacement::OnCreation if no_guess.is_some() => {
+                let center = Coordinates {
+                    x: width / 2,
+                    y: height / 2,
+                };
+                game.generate(bomb_count, center, true);
+                game.opening = Some(center);
+            }
+            BombPlacement::OnCreation => game.tile_map.set_bombs(bomb_count, seed),
+            BombPlacement::FirstClick { safe_square } => {
+                game.pending_bombs = Some(PendingBombs {
+                    bomb_count,
+                    safe_square,
+                })
+            }
+        }
+        game
+    }
+
+    /// Starts a game on an already generated `tile_map`, every tile being covered
+    pub fn from_tile_map(tile_map: TileMap, seed: u64) -> Self {
+        let (width, height) = (tile_map.width(), tile_map.height());
+        Self {
+            tile_map,
+            seed,
+            pending_bombs: None,
+            no_guess: None,
+            opening: None,
+            covered: TileSet::full(width, height),
+            flagged: TileSet::empty(width, height),
+            outcome: None,
+            undo: false,
+            history: Vec::new(),
+            undone: Vec::new(),
+        }
+    }
+
+    /// The same game with every tile covered again
+    pub fn restarted(&self) -> Self {
+        let mut game = Self::from_tile_map(self.tile_map.clone(), self.seed);
+        game.pending_bombs = self.pending_bombs;
+        game.no_guess = self.no_guess;
+        game.opening = self.opening;
+        game.undo = self.undo;
+        game
+    }
+
+    /// Records the next actions to undo them, or forgets the recorded ones if `undo` is off
+    pub fn set_undo(&mut self, undo: bool) {
+        self.undo = undo;
+        if !undo {
+            self.history.clear();
+            self.undone.clear();
+        }
+    }
+
+    // Getter for `tile_map`
+    pub fn tile_map(&self) -> &TileMap {
+        &self.tile_map
+    }
+
+    // Getter for `seed`
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    // Getter for `outcome`
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    /// Number of bombs of the game, placed or not
+    pub fn bomb_count(&self) -> u16 {
+        match self.pending_bombs {
+            Some(pending) => pending.bomb_count,
+            None => self.tile_map.bomb_count(),
+        }
+    }
+
+    /// Tile to uncover first to solve the board without guessing, for no guess boards
+    /// generated on creation
+    pub fn opening(&self) -> Option<Coordinates> {
+        self.opening
+    }
+
+    /// Tile uncovered when the board is spawned: the opening of no guess boards, or a safe
+    /// tile with `safe_start`. Started games have none
+    pub fn start_tile(&self, safe_start: bool) -> Option<Coordinates> {
+        if self.has_started() {
+            None
+        } else if self.opening.is_some() {
+            self.opening
+        } else if !safe_start {
+            None
+        } else if self.has_pending_bombs() {
+            // The bombs are placed around the first uncovered tile, we start from the center one
+            Some(Coordinates {
+                x: self.tile_map.width() / 2,
+                y: self.tile_map.height() / 2,
+            })
+        } else {
+            self.covered_tiles()
+                .filter(|c| self.tile_map.tile_at(*c) == Some(&Tile::Empty))
+                .min()
+        }
+    }
+
+    /// Was a tile uncovered
+    pub fn has_started(&self) -> bool {
+        self.covered.len() < self.tile_map.tile_count()
+    }
+
+    /// Are the bombs still waiting for the first uncovered tile
+    pub fn has_pending_bombs(&self) -> bool {
+        self.pending_bombs.is_some()
+    }
+
+    pub fn is_covered(&self, coords: Coordinates) -> bool {
+        self.covered.contains(coords)
+    }
+
+    pub fn is_flagged(&self, coords: Coordinates) -> bool {
+        self.flagged.contains(coords)
+    }
+
+    // Getter for `covered`
+    pub fn covered(&self) -> &TileSet {
+        &self.covered
+    }
+
+    // Getter for `flagged`
+    pub fn flagged(&self) -> &TileSet {
+        &self.flagged
+    }
+
+    /// Covered tiles, line by line from the bottom
+    pub fn covered_tiles(&self) -> impl Iterator<Item = Coordinates> + '_ {
+        self.covered.iter()
+    }
+
+    /// Flagged tiles, line by line from the bottom
+    pub fn flagged_tiles(&self) -> impl Iterator<Item = Coordinates> + '_ {
+        self.flagged.iter()
+    }
+
+    pub fn flag_count(&self) -> usize {
+        self.flagged.len()
+    }
+
+    /// Places the pending bombs around `coords`, returns `false` if the bombs were already placed
+    pub fn place_pending_bombs(&mut self, coords: Coordinates) -> bool {
+        let pending = match self.pending_bombs.take() {
+            None => return false,
+            Some(p) => p,
+        };
+        self.generate(pending.bomb_count, coords, pending.safe_square);
+        true
+    }
+
+    /// Places the bombs, keeping `start` and its neighbors if `safe_square` free of bombs.
+    ///
+    /// No guess boards always keep the square free, and are generated again until the
+    /// solver clears them from `start`
+    fn generate(&mut self, bomb_count: u16, start: Coordinates, safe_square: bool) {
+        let mut safe = vec![start];
+        if safe_square || self.no_guess.is_some() {
+            safe.extend(self.tile_map.safe_square_at(start));
+        }
+        let no_guess = match self.no_guess {
+            None => {
+                self.tile_map.set_bombs_avoiding(bomb_count, self.seed, &safe);
+                return;
+            }
+            Some(n) => n,
+        };
+        let (width, height) = (self.tile_map.width(), self.tile_map.height());
+        let topology = self.tile_map.topology();
+        // The first attempt uses our seed, the next ones are derived from it
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut seed = self.seed;
+        let mut bomb_count = bomb_count;
+        loop {
+            for _ in 0..no_guess.max_attempts.max(1) {
+                self.tile_map = TileMap::with_topology(width, height, topology);
+                self.tile_map.set_bombs_avoiding(bomb_count, seed, &safe);
+                if solver::solves_from(&self.tile_map, start) {
+                    return;
+                }
+                seed = rng.gen();
+            }
+            match no_guess.fallback {
+                NoGuessFallback::KeepLast => return,
+                NoGuessFallback::RemoveBomb => bomb_count = bomb_count.saturating_sub(1),
+            }
+        }
+    }
+
+    /// Uncovers the tile at `coords`, propagating to the neighbors of empty tiles.
+    ///
+    /// Flagged tiles and finished games are left untouched
+    pub fn reveal(&mut self, coords: Coordinates) -> Reveal {
+        if self.outcome.is_some() || !self.is_covered(coords) || self.is_flagged(coords) {
+            return Reveal::default();
+        }
+        self.place_pending_bombs(coords);
+        let mut step = self.step();
+        self.uncover_from(coords, &mut step);
+        self.push_step(step)
+    }
+
+    /// Flags or unflags a covered tile, returning whether the tile is now flagged
+    pub fn toggle_flag(&mut self, coords: Coordinates) -> Option<bool> {
+        if self.outcome.is_some() || !self.is_covered(coords) {
+            return None;
+        }
+        let mut step = self.step();
+        step.flags.push(coords);
+        self.push_step(step);
+        Some(self.toggle(coords))
+    }
+
+    /// Uncovers every unflagged neighbor of an uncovered bomb neighbor tile, if the
+    /// count of flagged neighbors matches its bomb count.
+    ///
+    /// Wrong flags make the chord uncover a bomb
+    pub fn chord(&mut self, coords: Coordinates) -> Reveal {
+        if self.outcome.is_some() || self.is_covered(coords) {
+            return Reveal::default();
+        }
+        let count = match self.tile_map.tile_at(coords) {
+            Some(Tile::BombNeighbor(count)) => *count as usize,
+            _ => return Reveal::default(),
+        };
+        let neighbors: Vec<Coordinates> = self
+            .tile_map
+            .safe_square_at(coords)
+            .filter(|c| self.is_covered(*c))
+            .collect();
+        let flags = neighbors.iter().filter(|c| self.is_flagged(**c)).count();
+        if flags != count {
+            return Reveal::default();
+        }
+        let mut step = self.step();
+        for neighbor in neighbors {
+            if self.outcome.is_some() {
+                break;
+            }
+            if self.is_covered(neighbor) && !self.is_flagged(neighbor) {
+                self.uncover_from(neighbor, &mut step);
+            }
+        }
+        self.push_step(step)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Covers the tiles uncovered by the last action and restores its flags and outcome,
+    /// returning the undone step
+    pub fn undo(&mut self) -> Option<Step> {
+        let step = self.history.pop()?;
+        for coords in &step.revealed {
+            self.covered.insert(*coords);
+        }
+        for coords in &step.flags {
+            self.toggle(*coords);
+        }
+        self.outcome = step.before;
+        self.undone.push(step.clone());
+        Some(step)
+    }
+
+    /// Applies the last undone action again, returning the redone step
+    pub fn redo(&mut self) -> Option<Step> {
+        let step = self.undone.pop()?;
+        for coords in &step.revealed {
+            self.covered.remove(*coords);
+        }
+        for coords in &step.flags {
+            self.toggle(*coords);
+        }
+        self.outcome = step.after;
+        self.history.push(step.clone());
+        Some(step)
+    }
+
+    /// Toggles the flag of `coords`, returning whether it is now flagged
+    fn toggle(&mut self, coords: Coordinates) -> bool {
+        if self.flagged.remove(coords) {
+            false
+        } else {
+            self.flagged.insert(coords);
+            true
+        }
+    }
+
+    /// Starts the step of a new action
+    fn step(&self) -> Step {
+        Step {
+            before: self.outcome,
+            ..Default::default()
+        }
+    }
+
+    /// Ends the step of an action, which can't be redone over anymore
+    fn push_step(&mut self, mut step: Step) -> Reveal {
+        step.after = self.outcome;
+        let reveal = Reveal {
+            revealed: step.revealed.clone(),
+            outcome: self.outcome,
+        };
+        if self.undo && step != self.step() {
+            self.undone.clear();
+            self.history.push(step);
+        }
+        reveal
+    }
+
+    /// Uncovers `coords` and the whole empty region around it into `step`, updating the outcome
+    fn uncover_from(&mut self, coords: Coordinates, step: &mut Step) {
+        let start = match self.tile_map.index(coords) {
+            None => return,
+            Some(i) => i,
+        };
+        if !self.covered.remove_index(start) {
+            return;
+        }
+        // Tiles are uncovered when queued, so each one is queued once
+        let mut queue = VecDeque::from([start]);
+        while let Some(index) = queue.pop_front() {
+            let coords = self.tile_map.coordinates(index);
+            if self.flagged.remove_index(index) {
+                step.flags.push(coords);
+            }
+            step.revealed.push(coords);
+            match self.tile_map.tiles()[index] {
+                Tile::Bomb => {
+                    self.outcome = Some(Outcome::Lost);
+                    return;
+                }
+                Tile::Empty => {
+                    for neighbor in self.tile_map.neighbor_indexes(index) {
+                        if self.covered.remove_index(neighbor) {
+                            queue.push_back(neighbor);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        if self.outcome.is_none() && self.covered.len() == self.tile_map.bomb_count() as usize {
+            self.outcome = Some(Outcome::Won);
+        }
+    }
+}
@@ -1,22 +1,26 @@
 // lib.rs
 mod bounds;
 pub mod components;
-mod events;
+pub mod events;
+pub mod game;
+pub mod probability;
 pub mod resources;
+pub mod solver;
 mod systems;
 use bevy::ecs::schedule::StateData;
 use bevy::log;
 use bevy::prelude::*;
-use bevy::utils::HashMap;
+use std::time::Duration;
 
 use crate::events::*;
 use bevy::math::Vec3Swizzles;
+use bevy::ecs::system::EntityCommands;
 use board::Board;
 use bounds::Bounds2;
 use components::*;
+use game::Game;
 use resources::*;
 use tile::*;
-use tile_map::*;
 
 use resources::BoardAssets;
 
@@ -27,54 +31,131 @@ pub struct BoardPlugin<T> {
 impl<T: StateData> Plugin for BoardPlugin<T> {
     fn build(&self, app: &mut App) {
         app.add_system_set(
-            SystemSet::on_enter(self.running_state.clone()).with_system(Self::create_board),
+            SystemSet::on_enter(self.running_state.clone())
+                .with_system(Self::create_board)
+                .with_system(systems::hud::spawn_hud),
         )
         // We handle input and trigger events only if the state is active
         .add_system_set(
             SystemSet::on_update(self.running_state.clone())
                 .with_system(systems::input::input_handling)
-                .with_system(systems::uncover::trigger_event_handler),
+                .with_system(systems::input::keyboard_cursor)
+                .with_system(systems::input::gamepad_cursor)
+                .with_system(systems::layout::relayout_board)
+                .with_system(systems::camera::camera_controls.after(systems::layout::relayout_board))
+                .with_system(
+                    systems::hint::hint_event_handler
+                        .after(systems::input::input_handling)
+                        .after(systems::input::keyboard_cursor)
+                        .after(systems::input::gamepad_cursor),
+                )
+                .with_system(
+                    systems::replay::record_actions
+                        .after(systems::input::input_handling)
+                        .after(systems::input::keyboard_cursor)
+                        .after(systems::input::gamepad_cursor)
+                        .before(Self::place_pending_bombs),
+                )
+                .with_system(
+                    Self::place_pending_bombs.before(systems::uncover::trigger_event_handler),
+                )
+                .with_system(systems::uncover::trigger_event_handler)
+                .with_system(systems::uncover::chord_event_handler)
+                .with_system(
+                    systems::undo::undo_event_handler
+                        .after(systems::uncover::trigger_event_handler)
+                        .after(systems::uncover::chord_event_handler),
+                )
+                // Boards are rebuilt after the events of the previous one are handled
+                .with_system(
+                    Self::board_events_handler
+                        .after(systems::uncover::trigger_event_handler)
+                        .after(systems::uncover::chord_event_handler),
+                )
+                .with_system(
+                    Self::replay_events_handler
+                        .after(systems::uncover::trigger_event_handler)
+                        .after(systems::uncover::chord_event_handler),
+                )
+                .with_system(systems::replay::feed_replay.after(Self::replay_events_handler))
+                // The safe start of a new board is told apart with the new board
+                .with_system(
+                    systems::hud::update_timer
+                        .before(Self::board_events_handler)
+                        .before(Self::replay_events_handler),
+                )
+                .with_system(
+                    systems::high_scores::record_high_score.after(systems::hud::update_timer),
+                ),
         )
         // We handle uncovering even if the state is inactive
         .add_system_set(
             SystemSet::on_in_stack_update(self.running_state.clone())
                 .with_system(systems::uncover::uncover_tiles)
+                .with_system(systems::uncover::delay_uncover)
                 .with_system(systems::mark::mark_tiles)
                 .with_system(systems::fail::fail)
-                .with_system(systems::completed::completed),
+                .with_system(systems::completed::completed)
+                .with_system(systems::overlay::probability_overlay)
+                .with_system(systems::chunks::draw_chunks)
+                .with_system(systems::status::update_status)
+                .with_system(systems::hud::update_hud)
+                .with_system(systems::save::save_board)
+                .with_system(systems::replay::save_replay),
         )
         .add_system_set(
-            SystemSet::on_exit(self.running_state.clone()).with_system(Self::cleanup_board),
+            SystemSet::on_exit(self.running_state.clone())
+                .with_system(Self::cleanup_board)
+                .with_system(systems::hud::despawn_hud),
         )
+        .init_resource::<ProbabilityOverlay>()
+        .init_resource::<GameStatus>()
+        .init_resource::<GameTimer>()
+        .init_resource::<ReplayRecorder>()
+        .init_resource::<InputBindings>()
+        .init_resource::<CameraControls>()
         .add_event::<TileTriggerEvent>()
         .add_event::<TileMarkEvent>()
+        .add_event::<TileChordEvent>()
         .add_event::<BombExplosionEvent>()
-        .add_event::<BoardCompletedEvent>();
+        .add_event::<BoardCompletedEvent>()
+        .add_event::<GameStatusChangedEvent>()
+        .add_event::<RestartBoardEvent>()
+        .add_event::<NewBoardEvent>()
+        .add_event::<SaveBoardEvent>()
+        .add_event::<LoadBoardEvent>()
+        .add_event::<HintEvent>()
+        .add_event::<UndoEvent>()
+        .add_event::<RedoEvent>()
+        .add_event::<SaveReplayEvent>()
+        .add_event::<PlayReplayEvent>()
+        .add_event::<ReplayControlEvent>();
+        // Hex tiles without their own texture are masked, when rendering
+        if let Some(mut images) = app.world.get_resource_mut::<Assets<Image>>() {
+            images.set_untracked(HEX_TILE_IMAGE_HANDLE, topology::hex_tile_image());
+        }
         #[cfg(feature = "debug")]
         {
             app.register_type::<Coordinates>()
                 .register_type::<Bomb>()
                 .register_type::<BombNeighbor>()
-                .register_type::<Uncover>();
+                .register_type::<Uncover>()
+                .register_type::<Hud>()
+                .register_type::<GameOver>()
+                .register_type::<Cursor>()
+                .register_type::<BoardCamera>();
         }
         log::info!("Loaded Board Plugin");
     }
 }
 
 impl<T> BoardPlugin<T> {
-    /// Computes a tile size that matches the window according to the tile map size
-    fn adaptative_tile_size(
-        window: Res<Windows>,
-        (min, max): (f32, f32),      // Tile size constraints
-        (width, height): (u16, u16), // Tile map dimensions
-    ) -> f32 {
-        let window = window.get_primary().expect("get window primary error");
-
-        let max_width = window.width() / width as f32;
-        let max_heigth = window.height() / height as f32;
-        max_width.min(max_heigth).clamp(min, max)
-    }
     fn cleanup_board(board: Res<Board>, mut commands: Commands) {
+        Self::despawn_board(&mut commands, &board);
+        commands.remove_resource::<ReplayPlayback>();
+    }
+    /// Despawns the board entities and removes the `Board` resource
+    fn despawn_board(commands: &mut Commands, board: &Board) {
         commands.entity(board.entity).despawn_recursive();
         commands.remove_resource::<Board>();
     }
@@ -84,50 +165,201 @@ impl<T> BoardPlugin<T> {
         board_options: Option<Res<BoardOptions>>,
         window: Res<Windows>,
         board_assets: Res<BoardAssets>,
+        mut timer: ResMut<GameTimer>,
+        mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
     ) {
         let options = match board_options {
             None => BoardOptions::default(), // If no options is set we use the default one
             Some(o) => o.clone(),
         };
+        let game = Self::new_game(&options);
+        *timer = GameTimer::default();
+        Self::spawn_board(
+            &mut commands,
+            game,
+            &options,
+            &window,
+            &board_assets,
+            &mut tile_trigger_ewr,
+        );
+    }
+    /// System rebuilding the board on `RestartBoardEvent`, `NewBoardEvent` and `LoadBoardEvent`
+    #[allow(clippy::too_many_arguments)]
+    fn board_events_handler(
+        mut commands: Commands,
+        board: Res<Board>,
+        board_options: Option<Res<BoardOptions>>,
+        window: Res<Windows>,
+        board_assets: Res<BoardAssets>,
+        mut timer: ResMut<GameTimer>,
+        mut restart_evr: EventReader<RestartBoardEvent>,
+        mut new_board_evr: EventReader<NewBoardEvent>,
+        mut load_board_evr: EventReader<LoadBoardEvent>,
+        mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    ) {
+        let restart = restart_evr.iter().count() > 0;
+        let new_board = new_board_evr.iter().last().cloned();
+        let saved_game = load_board_evr
+            .iter()
+            .last()
+            .and_then(|event| match SavedGame::load(&event.0) {
+                Ok(saved_game) => Some(saved_game),
+                Err(e) => {
+                    log::error!("Failed to load {}: {}", event.0.display(), e);
+                    None
+                }
+            });
+        let mut options = match board_options {
+            None => BoardOptions::default(),
+            Some(o) => o.clone(),
+        };
+        let mut elapsed = Duration::ZERO;
+        let game = match (saved_game, new_board) {
+            (Some(saved_game), _) => {
+                commands.insert_resource(saved_game.options.clone());
+                options = saved_game.options;
+                elapsed = saved_game.elapsed;
+                saved_game.game
+            }
+            (None, Some(NewBoardEvent(Some(new_options)))) => {
+                commands.insert_resource(new_options.clone());
+                options = new_options;
+                Self::new_game(&options)
+            }
+            (None, Some(NewBoardEvent(None))) => {
+                options.seed = None;
+                Self::new_game(&options)
+            }
+            (None, None) if restart => board.game.restarted(),
+            (None, None) => return,
+        };
+        log::info!("Rebuilding board");
+        // Loaded games keep their time, which counts again from their next move
+        *timer = GameTimer {
+            elapsed,
+            running: false,
+        };
+        // The player takes the new board over from any replay
+        commands.remove_resource::<ReplayPlayback>();
+        Self::despawn_board(&mut commands, &board);
+        Self::spawn_board(
+            &mut commands,
+            game,
+            &options,
+            &window,
+            &board_assets,
+            &mut tile_trigger_ewr,
+        );
+    }
+    /// System starting replays on `PlayReplayEvent` and controlling them on `ReplayControlEvent`
+    #[allow(clippy::too_many_arguments)]
+    fn replay_events_handler(
+        mut commands: Commands,
+        board: Res<Board>,
+        window: Res<Windows>,
+        board_assets: Res<BoardAssets>,
+        playback: Option<ResMut<ReplayPlayback>>,
+        mut timer: ResMut<GameTimer>,
+        mut play_replay_evr: EventReader<PlayReplayEvent>,
+        mut replay_control_evr: EventReader<ReplayControlEvent>,
+        mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    ) {
+        let replay = play_replay_evr
+            .iter()
+            .last()
+            .and_then(|event| match Replay::load(&event.0) {
+                Ok(replay) => Some(replay),
+                Err(e) => {
+                    log::error!("Failed to load {}: {}", event.0.display(), e);
+                    None
+                }
+            });
+        let (game, options) = if let Some(replay) = replay {
+            log::info!("Playing replay");
+            let options = replay.options.clone();
+            let mut playback = ReplayPlayback::new(replay);
+            let game = playback.seek(Duration::ZERO);
+            commands.insert_resource(options.clone());
+            commands.insert_resource(playback);
+            (game, options)
+        } else if let Some(mut playback) = playback {
+            let mut game = None;
+            for event in replay_control_evr.iter() {
+                match *event {
+                    ReplayControlEvent::TogglePause => playback.paused = !playback.paused,
+                    ReplayControlEvent::SetSpeed(speed) => playback.speed = speed,
+                    ReplayControlEvent::Seek(time) => game = Some(playback.seek(time)),
+                    ReplayControlEvent::Stop => {
+                        log::info!("Stopping replay");
+                        commands.remove_resource::<ReplayPlayback>();
+                    }
+                }
+            }
+            match game {
+                None => return,
+                Some(g) => (g, playback.replay().options.clone()),
+            }
+        } else {
+            return;
+        };
+        *timer = GameTimer::default();
+        Self::despawn_board(&mut commands, &board);
+        Self::spawn_board(
+            &mut commands,
+            game,
+            &options,
+            &window,
+            &board_assets,
+            &mut tile_trigger_ewr,
+        );
+    }
+    /// Generates the game of a new board
+    fn new_game(options: &BoardOptions) -> Game {
         // Tilemap generation
-        let mut tile_map = TileMap::empty(options.map_size.0, options.map_size.1);
-        tile_map.set_bombs(options.bomb_count);
+        let seed = options.seed.unwrap_or_else(rand::random);
+        log::info!("board seed: {}", seed);
+        Game::new(
+            options.map_size,
+            options.bomb_count,
+            seed,
+            options.bomb_placement,
+            options.no_guess,
+            options.topology,
+        )
+    }
+    /// Spawns the board entities of `game` and inserts the `Board` resource
+    fn spawn_board(
+        commands: &mut Commands,
+        mut game: Game,
+        options: &BoardOptions,
+        window: &Windows,
+        board_assets: &BoardAssets,
+        tile_trigger_ewr: &mut EventWriter<TileTriggerEvent>,
+    ) {
+        game.set_undo(options.undo);
+        let tile_map = game.tile_map();
         #[cfg(feature = "debug")]
         // Tilemap debugging
         log::info!("{}", tile_map.console_output());
 
-        let tile_size = match options.tile_size {
-            TileSize::Fixed(v) => v,
-            TileSize::Adaptive { min, max } => Self::adaptative_tile_size(
-                window,
-                (min, max),
-                (tile_map.width(), tile_map.height()),
-            ),
-        };
+        let topology = tile_map.topology();
+        let dimensions = (tile_map.width(), tile_map.height());
+        let tile_size = options
+            .tile_size
+            .size(window.get_primary(), topology, dimensions);
 
         // We deduce the size of the complete board
-        let board_size = Vec2::new(
-            tile_map.width() as f32 * tile_size,
-            tile_map.height() as f32 * tile_size,
-        );
+        let board_size = topology.board_size(dimensions, tile_size);
         log::info!("board size: {}", board_size);
         // We define the board anchor position (bottom left)
-        let board_position = match options.position {
-            BoardPosition::Centered { offset } => {
-                Vec3::new(-(board_size.x / 2.), -(board_size.y / 2.), 0.) + offset
-            }
-            BoardPosition::Custom(p) => p,
-        };
+        let board_position = options.position.anchor(board_size);
 
-        let mut covered_tiles =
-            HashMap::with_capacity((tile_map.width() * tile_map.height()).into());
-
-        let mut safe_start = None;
+        let mut covers = vec![None; tile_map.tile_count()];
 
         let board_entry = commands
             .spawn(SpatialBundle {
                 visibility: Visibility::VISIBLE,
-                transform: Transform::from_translation(board_position.into()),
+                transform: Transform::from_translation(board_position),
                 ..Default::default()
             })
             .insert(Name::new("Board"))
@@ -146,35 +378,59 @@ impl<T> BoardPlugin<T> {
                     })
                     .insert(Name::new("Background"));
 
-                Self::spawn_tiles(
-                    parent,
-                    &tile_map,
-                    tile_size,
-                    options.tile_padding,
-                    &board_assets,
-                    &mut covered_tiles,
-                    &mut safe_start,
-                );
+                match options.render_mode {
+                    RenderMode::Chunks { size } if topology != Topology::Hex => {
+                        Self::spawn_chunks(parent, tile_map, tile_size, size)
+                    }
+                    _ => Self::spawn_tiles(
+                        parent,
+                        &game,
+                        tile_size,
+                        options.tile_padding,
+                        board_assets,
+                        &mut covers,
+                    ),
+                }
+
+                // The keyboard cursor starts hidden on the center tile
+                let coordinates = Coordinates {
+                    x: tile_map.width() / 2,
+                    y: tile_map.height() / 2,
+                };
+                let position = topology.tile_center(coordinates, tile_size);
+                parent
+                    .spawn(SpriteBundle {
+                        sprite: Sprite {
+                            color: board_assets.cursor_material.color,
+                            custom_size: Some(topology.sprite_size(tile_size)),
+                            ..Default::default()
+                        },
+                        texture: topology.sprite_texture(&board_assets.cursor_material),
+                        transform: Transform::from_translation(position.extend(5.)),
+                        visibility: Visibility::INVISIBLE,
+                        ..Default::default()
+                    })
+                    .insert(Name::new("Cursor"))
+                    .insert(Cursor { coordinates });
             })
             .id();
 
-        if options.safe_start {
-            if let Some(entity) = safe_start {
-                commands.entity(entity).insert(Uncover);
-            }
+        let start_tile = game.start_tile(options.safe_start);
+        if let Some(coords) = start_tile {
+            tile_trigger_ewr.send(TileTriggerEvent(coords));
         }
 
         commands.insert_resource(Board {
-            tile_map,
+            game,
             bounds: Bounds2 {
                 position: board_position.xy(),
                 size: board_size,
             },
             tile_size,
-            covered_tiles,
+            covers,
             entity: board_entry,
-            marked_tiles: Vec::new(),
-            need_stop_listening_pressed: false,
+            start_tile,
+            changed_tiles: Vec::new(),
         });
     }
     /// Generates the bomb counter text 2D Bundle for a given value
@@ -201,91 +457,132 @@ impl<T> BoardPlugin<T> {
             ..Default::default()
         }
     }
+    /// Spawns the tiles of `game`, with a cover on the covered tiles and a flag on the flagged ones
     fn spawn_tiles(
         parent: &mut ChildBuilder,
-        tile_map: &TileMap,
+        game: &Game,
         size: f32,
         padding: f32,
         board_assets: &BoardAssets,
-        covered_tiles: &mut HashMap<Coordinates, Entity>,
-        safe_start_enty: &mut Option<Entity>,
+        covers: &mut [Option<Entity>],
     ) {
+        let topology = game.tile_map().topology();
         // Tiles
-        for (y, line) in tile_map.iter().enumerate() {
-            for (x, tile) in line.iter().enumerate() {
-                let coordinates = Coordinates {
-                    x: x as u16,
-                    y: y as u16,
-                };
+        for (index, tile) in game.tile_map().tiles().iter().enumerate() {
+            let coordinates = game.tile_map().coordinates(index);
+            let (x, y) = (coordinates.x, coordinates.y);
 
-                let tile_bundle = SpriteBundle {
-                    sprite: Sprite {
-                        color: board_assets.tile_material.color,
-                        custom_size: Some(Vec2::splat(size - padding)),
+            let tile_bundle = SpriteBundle {
+                sprite: Sprite {
+                    color: board_assets.tile_material.color,
+                    custom_size: Some(topology.sprite_size(size - padding)),
 
-                        ..Default::default()
-                    },
-                    transform: Transform::from_xyz(
-                        (x as f32 * size) + (size / 2.),
-                        (y as f32 * size) + (size / 2.),
-                        1.,
-                    ),
-                    texture: board_assets.tile_material.texture.clone(),
                     ..Default::default()
-                };
+                },
+                transform: Transform::from_translation(
+                    topology.tile_center(coordinates, size).extend(1.),
+                ),
+                texture: topology.sprite_texture(&board_assets.tile_material),
+                ..Default::default()
+            };
 
-                let mut cmd = parent.spawn(tile_bundle);
+            let mut cmd = parent.spawn(tile_bundle);
 
-                cmd.insert(Name::new(format!("Tile ({}, {})", x, y)))
-                    .insert(coordinates);
+            cmd.insert(Name::new(format!("Tile ({}, {})", x, y)))
+                .insert(coordinates);
 
+            if game.is_covered(coordinates) {
                 cmd.with_children(|parent| {
-                    let entity = parent
-                        .spawn(SpriteBundle {
-                            sprite: Sprite {
-                                custom_size: Some(Vec2::splat(size - padding)),
-                                color: board_assets.covered_tile_material.color,
-                                ..Default::default()
-                            },
-                            transform: Transform::from_xyz(0., 0., 2.),
-                            texture: board_assets.covered_tile_material.texture.clone(),
-                            ..Default::default()
-                        })
-                        .insert(Name::new("Tile Cover"))
-                        .id();
-                    covered_tiles.insert(coordinates, entity);
-
-                    if safe_start_enty.is_none() && *tile == Tile::Empty {
-                        *safe_start_enty = Some(entity);
+                    let mut cover = parent.spawn(systems::uncover::cover_bundle(
+                        board_assets,
+                        topology,
+                        size - padding,
+                    ));
+                    cover.insert(Name::new("Tile Cover"));
+                    if game.is_flagged(coordinates) {
+                        cover.with_children(|parent| {
+                            parent
+                                .spawn(systems::mark::flag_bundle(board_assets, size))
+                                .insert(Name::new("Flag"));
+                        });
                     }
+                    covers[index] = Some(cover.id());
                 });
+            }
 
-                match tile {
-                    Tile::Bomb => {
-                        cmd.insert(Bomb).with_children(|parent| {
-                            parent.spawn(SpriteBundle {
-                                sprite: Sprite {
-                                    custom_size: Some(Vec2::splat(size - padding)),
-                                    ..Default::default()
-                                },
-                                transform: Transform::from_xyz(0., 0., 1.),
-                                texture: board_assets.bomb_material.texture.clone(),
-                                ..Default::default()
-                            });
-                        });
-                    }
-                    Tile::BombNeighbor(count) => {
-                        let bomb_neighbor = BombNeighbor { count: *count };
-                        cmd.insert(bomb_neighbor).with_children(|parent| {
-                            parent.spawn(Self::bomb_count_text_bundle(
-                                *count,
-                                board_assets,
-                                size - padding,
-                            ));
-                        });
-                    }
-                    _ => (),
+            Self::insert_tile_content(&mut cmd, tile, size - padding, board_assets);
+        }
+    }
+    /// Spawns the chunks of `size` tiles covering `tile_map`, drawn by `systems::chunks::draw_chunks`
+    fn spawn_chunks(parent: &mut ChildBuilder, tile_map: &TileMap, tile_size: f32, size: u16) {
+        let size = size.max(1);
+        for y in (0..tile_map.height()).step_by(size as usize) {
+            for x in (0..tile_map.width()).step_by(size as usize) {
+                let chunk_size = (size.min(tile_map.width() - x), size.min(tile_map.height() - y));
+                parent
+                    .spawn(SpatialBundle {
+                        transform: Transform::from_xyz(x as f32 * tile_size, y as f32 * tile_size, 1.),
+                        ..Default::default()
+                    })
+                    .insert(Name::new(format!("Chunk ({}, {})", x, y)))
+                    .insert(TileChunk::new(Coordinates { x, y }, chunk_size));
+            }
+        }
+    }
+    /// Inserts the `Bomb` or `BombNeighbor` component of `tile` and its sprite or text child
+    fn insert_tile_content(
+        cmd: &mut EntityCommands,
+        tile: &Tile,
+        size: f32,
+        board_assets: &BoardAssets,
+    ) {
+        match tile {
+            Tile::Bomb => {
+                cmd.insert(Bomb).with_children(|parent| {
+                    parent.spawn(SpriteBundle {
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::splat(size)),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_xyz(0., 0., 1.),
+                        texture: board_assets.bomb_material.texture.clone(),
+                        ..Default::default()
+                    });
+                });
+            }
+            Tile::BombNeighbor(count) => {
+                let bomb_neighbor = BombNeighbor { count: *count };
+                cmd.insert(bomb_neighbor).with_children(|parent| {
+                    parent.spawn(Self::bomb_count_text_bundle(*count, board_assets, size));
+                });
+            }
+            _ => (),
+        };
+    }
+    /// System placing the pending bombs on the first triggered tile, see `BombPlacement::FirstClick`
+    fn place_pending_bombs(
+        mut commands: Commands,
+        mut board: ResMut<Board>,
+        board_assets: Res<BoardAssets>,
+        mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+        tiles: Query<(Entity, &Coordinates, &Sprite)>,
+    ) {
+        for trigger_event in tile_trigger_evr.iter() {
+            if board.tile_to_uncover(&trigger_event.0).is_none()
+                || !board.game.place_pending_bombs(trigger_event.0)
+            {
+                continue;
+            }
+            let tile_map = board.game.tile_map();
+            #[cfg(feature = "debug")]
+            log::info!("{}", tile_map.console_output());
+            for (entity, coordinates, sprite) in tiles.iter() {
+                let tile = match tile_map.tile_at(*coordinates) {
+                    None => continue,
+                    Some(t) => t,
                 };
+                let size = sprite.custom_size.unwrap_or(Vec2::splat(board.tile_size)).x;
+                Self::insert_tile_content(&mut commands.entity(entity), tile, size, &board_assets);
             }
         }
     }
@@ -0,0 +1,124 @@
+// probability.rs
+use crate::components::Coordinates;
+use crate::solver::Solver;
+use std::collections::HashMap;
+
+/// Computes the probability of every covered unflagged tile to hold a bomb.
+///
+/// Every configuration of the frontier (covered tiles next to uncovered bomb neighbors) is
+/// weighted by the number of ways to place the remaining bombs on the other covered tiles.
+/// Frontier groups too large to be enumerated are counted as such other tiles, as is the whole
+/// frontier once the solver node budget runs out.
+///
+/// Returns `None` if no configuration matches the board, which happens with wrong flags
+pub fn bomb_probabilities(solver: &Solver) -> Option<HashMap<Coordinates, f64>> {
+    let known = solver.deduce();
+    let remaining = solver.remaining_bombs(&known)?;
+    let constraints = solver.constraints(&known);
+    let mut groups = solver.enumerate(&constraints, remaining);
+    if solver.nodes_left() == 0 {
+        // Out of budget, every unknown tile gets the plain bomb density
+        groups.clear();
+    }
+
+    let mut probabilities: HashMap<Coordinates, f64> = known
+        .iter()
+        .map(|(coords, bomb)| (*coords, if *bomb { 1. } else { 0. }))
+        .collect();
+    let interior: Vec<Coordinates> = solver
+        .unknown_tiles(&known)
+        .filter(|c| !groups.iter().any(|g| g.tiles.contains(c)))
+        .collect();
+
+    // Weight of each frontier bomb count: the ways to place the other bombs in the interior
+    let weights = interior_weights(interior.len(), remaining);
+    let counts: Vec<Vec<f64>> = groups
+        .iter()
+        .map(|g| g.solutions.iter().map(|s| *s as f64).collect())
+        .collect();
+    let all = convolve_all(counts.iter());
+    let total: f64 = weighted_sum(&all, &weights);
+    if total == 0. {
+        return None;
+    }
+
+    for (i, group) in groups.iter().enumerate() {
+        // Frontier configurations of every other group
+        let others = convolve_all(
+            counts
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, c)| c),
+        );
+        for (t, tile) in group.tiles.iter().enumerate() {
+            let bombs: Vec<f64> = group.tile_bombs.iter().map(|b| b[t] as f64).collect();
+            let with_bomb = weighted_sum(&convolve(&bombs, &others), &weights);
+            probabilities.insert(*tile, with_bomb / total);
+        }
+    }
+
+    if !interior.is_empty() {
+        // Every interior tile has the same probability: the mean of its bomb share
+        let bombs: f64 = all
+            .iter()
+            .enumerate()
+            .filter_map(|(m, ways)| {
+                let left = remaining.checked_sub(m)?;
+                Some(ways * weights.get(m)? * left as f64)
+            })
+            .sum();
+        let probability = bombs / total / interior.len() as f64;
+        for coords in interior {
+            probabilities.insert(coords, probability);
+        }
+    }
+    Some(probabilities)
+}
+
+/// Relative number of ways to place `remaining - m` bombs on `interior` tiles, indexed by `m`.
+///
+/// The weights are scaled down to keep them in the `f64` range on large boards
+fn interior_weights(interior: usize, remaining: usize) -> Vec<f64> {
+    let ln_factorials: Vec<f64> = (0..=interior)
+        .scan(0., |acc, n| {
+            *acc += (n.max(1) as f64).ln();
+            Some(*acc)
+        })
+        .collect();
+    let ln_binomial = |k: usize| -> Option<f64> {
+        let rest = interior.checked_sub(k)?;
+        Some(ln_factorials[interior] - ln_factorials[k] - ln_factorials[rest])
+    };
+    let ln_weights: Vec<Option<f64>> = (0..=remaining)
+        .map(|m| ln_binomial(remaining - m))
+        .collect();
+    let max = ln_weights
+        .iter()
+        .flatten()
+        .copied()
+        .fold(f64::NEG_INFINITY, f64::max);
+    ln_weights
+        .into_iter()
+        .map(|w| w.map_or(0., |w| (w - max).exp()))
+        .collect()
+}
+
+fn weighted_sum(counts: &[f64], weights: &[f64]) -> f64 {
+    counts.iter().zip(weights.iter()).map(|(c, w)| c * w).sum()
+}
+
+/// Number of configurations of two independent groups, indexed by their total bomb count
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            result[i + j] += x * y;
+        }
+    }
+    result
+}
+
+fn convolve_all<'a>(counts: impl Iterator<Item = &'a Vec<f64>>) -> Vec<f64> {
+    counts.fold(vec![1.], |acc, c| convolve(&acc, c))
+}
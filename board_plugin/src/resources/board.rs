@@ -1,93 +1,107 @@
 // board.rs
 use crate::bounds::Bounds2;
-use crate::{Coordinates, TileMap};
+use crate::game::{Game, Outcome};
+use crate::Coordinates;
 use bevy::prelude::*;
-use bevy::utils::HashMap;
-use bevy::log;
 
 #[derive(Debug, Resource)]
 pub struct Board {
-    pub tile_map: TileMap,
+    /// Game rules and state, the board only renders it
+    pub game: Game,
     pub bounds: Bounds2,
     pub tile_size: f32,
-    pub covered_tiles: HashMap<Coordinates, Entity>,
+    /// Cover entities of the covered tiles, by tile index
+    pub covers: Vec<Option<Entity>>,
     pub entity: Entity,
-    pub marked_tiles: Vec<Coordinates>,
-    pub need_stop_listening_pressed: bool,
+    /// Tile uncovered when the board is created, see `BoardOptions::safe_start`
+    pub(crate) start_tile: Option<Coordinates>,
+    /// Tiles revealed, covered again or (un)flagged since the last `RenderMode::Chunks` draw
+    pub(crate) changed_tiles: Vec<Coordinates>,
 }
 
 impl Board {
-    /// We try to mark or unmark a tile, returning the entity and if the tile is marked
-    pub fn try_toggle_mark(&mut self, coords: &Coordinates) -> Option<(Entity, bool)> {
-        let entity = *self.covered_tiles.get(coords)?;
-        let mark = if self.marked_tiles.contains(coords) {
-            self.unmark_tile(coords)?;
-            false
-        } else {
-            self.marked_tiles.push(*coords);
-            true
-        };
+    /// We try to mark or unmark a tile, returning the cover entity if any and if the tile is marked
+    pub fn try_toggle_mark(&mut self, coords: &Coordinates) -> Option<(Option<Entity>, bool)> {
+        let entity = self.cover(*coords);
+        let mark = self.game.toggle_flag(*coords)?;
+        self.changed_tiles.push(*coords);
         Some((entity, mark))
     }
-    /// Removes the `coords` from `marked_tiles`
-    fn unmark_tile(&mut self, coords: &Coordinates) -> Option<Coordinates> {
-        let pos = match self.marked_tiles.iter().position(|a| a == coords) {
-            None => {
-                log::error!("Failed to unmark tile at {}", coords);
-                return None;
-            }
-            Some(p) => p,
-        };
-        Some(self.marked_tiles.remove(pos))
-    }
 
     /// Is the board complete
     pub fn is_completed(&self) -> bool {
-        println!("bomb: {}, tiles: {}", &self.tile_map.bomb_count(), &self.covered_tiles.len());
-        self.tile_map.bomb_count() as usize == self.covered_tiles.len()
+        self.game.outcome() == Some(Outcome::Won)
     }
-    /// Translates a mouse position to board coordinates
-    pub fn mouse_position(&self, window: &Window, position: Vec2) -> Option<Coordinates> {
+    /// Translates a mouse position to board coordinates, through the `camera` showing the board.
+    ///
+    /// Without a camera the default one is assumed, centered at the origin
+    pub fn mouse_position(
+        &self,
+        window: &Window,
+        camera: Option<(&Camera, &GlobalTransform)>,
+        position: Vec2,
+    ) -> Option<Coordinates> {
         // Window to world space
-        let window_size = Vec2::new(window.width(), window.height());
-        let position = position - window_size / 2.;
+        let position = match camera {
+            Some((camera, transform)) => camera.viewport_to_world(transform, position)?.origin.truncate(),
+            None => position - Vec2::new(window.width(), window.height()) / 2.,
+        };
 
         // Bounds check
         if !self.bounds.in_bounds(position) {
             return None;
         }
         // World space to board space
-        let coordinates = position - self.bounds.position;
-        Some(Coordinates {
-            x: (coordinates.x / self.tile_size) as u16,
-            y: (coordinates.y / self.tile_size) as u16,
-        })
+        let tile_map = self.game.tile_map();
+        tile_map.topology().tile_at(
+            position - self.bounds.position,
+            self.tile_size,
+            (tile_map.width(), tile_map.height()),
+        )
     }
     /// Retrieves a covered tile entity
-    pub fn tile_to_uncover(&self, coords: &Coordinates) -> Option<&Entity> {
-        if self.marked_tiles.contains(coords) {
+    pub fn tile_to_uncover(&self, coords: &Coordinates) -> Option<Entity> {
+        if self.game.is_flagged(*coords) {
             None
         } else {
-            self.covered_tiles.get(coords)
+            self.cover(*coords)
         }
       }
-  
-      /// We try to uncover a tile, returning the entity
-      pub fn try_uncover_tile(&mut self, coords: &Coordinates) -> Option<Entity> {
-          if self.marked_tiles.contains(coords) {
-            self.unmark_tile(coords);
+
+      /// Cover entity of a covered tile
+      pub fn cover(&self, coords: Coordinates) -> Option<Entity> {
+          *self.covers.get(self.game.tile_map().index(coords)?)?
+      }
+
+      /// Sets the cover entity of a covered tile
+      pub fn set_cover(&mut self, coords: Coordinates, entity: Entity) {
+          if let Some(index) = self.game.tile_map().index(coords) {
+              self.covers[index] = Some(entity);
           }
-          self.covered_tiles.remove(coords)
       }
-  
-      /// We retrieve the adjacent covered tile entities of `coord`
-      pub fn adjacent_covered_tiles(&self, coord: Coordinates) -> Vec<Entity> {
-          self.tile_map
-              .safe_square_at(coord)
-              .filter_map(|c| self.covered_tiles.get(&c))
-              .copied()
+
+      /// We remove the cover entity of a tile, returning it
+      pub fn take_cover(&mut self, coords: Coordinates) -> Option<Entity> {
+          let index = self.game.tile_map().index(coords)?;
+          self.covers[index].take()
+      }
+
+      /// We remove the cover entities of the `revealed` tiles, returning them
+      pub fn take_covers(&mut self, revealed: &[Coordinates]) -> Vec<Entity> {
+          revealed
+              .iter()
+              .filter_map(|c| self.take_cover(*c))
               .collect()
       }
+
+      /// Cover entities with their tile coordinates
+      pub fn cover_entities(&self) -> impl Iterator<Item = (Coordinates, Entity)> + '_ {
+          let tile_map = self.game.tile_map();
+          self.covers
+              .iter()
+              .enumerate()
+              .filter_map(|(i, e)| Some((tile_map.coordinates(i), (*e)?)))
+      }
       pub fn get_bound (&self) -> &Vec2 {
         &self.bounds.position
       }
@@ -99,7 +113,4 @@ impl Board {
         let size = self.get_size();
         Vec2::new(position.x + size.x, position.y + size.y)
       }
-      pub fn set_need_stop_state (&mut self, state: bool) {
-        self.need_stop_listening_pressed = state;
-      }
-}
\ No newline at end of file
+}
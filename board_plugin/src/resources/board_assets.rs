@@ -18,6 +18,16 @@ impl Default for SpriteMaterial {
     }
 }
 
+/// Style of the mines counter and timer display
+#[derive(Debug, Clone)]
+pub struct HudStyle {
+    pub font: Handle<Font>,
+    pub font_size: f32,
+    pub color: Color,
+    /// Position of the display in the window
+    pub position: UiRect,
+}
+
 /// Assets for the board. Must be used as a resource.
 ///
 /// Use the loader for partial setup
@@ -25,26 +35,32 @@ impl Default for SpriteMaterial {
 pub struct BoardAssets {
     /// Label
     pub label: String,
-    ///
+    /// Board background material
     pub board_material: SpriteMaterial,
-    ///
+    /// Uncovered tile material
     pub tile_material: SpriteMaterial,
-    ///
+    /// Covered tile material
     pub covered_tile_material: SpriteMaterial,
-    ///
+    /// Bomb counter text font
     pub bomb_counter_font: Handle<Font>,
-    ///
+    /// Bomb counter text colors, indexed by counter value
     pub bomb_counter_colors: Vec<Color>,
-    ///
+    /// Flag material
     pub flag_material: SpriteMaterial,
-    ///
+    /// Bomb material
     pub bomb_material: SpriteMaterial,
-    ///
+    /// Game over text material
     pub fail_material: SpriteMaterial,
-    ///
+    /// Game over board cover material
     pub fail_cover_board_material: SpriteMaterial,
-    ///
+    /// Game over text font size
     pub game_over_font_size: f32,
+    /// Probability overlay colors, from safe to bomb
+    pub probability_colors: (Color, Color),
+    /// Mines counter and timer display
+    pub hud: HudStyle,
+    /// Keyboard cursor highlight material
+    pub cursor_material: SpriteMaterial,
 }
 
 impl BoardAssets {
@@ -59,6 +75,25 @@ impl BoardAssets {
         ]
     }
 
+    /// Default probability overlay colors
+    pub fn default_probability_colors() -> (Color, Color) {
+        (Color::GREEN, Color::RED)
+    }
+
+    /// Blends the probability overlay colors matching a bomb probability
+    pub fn probability_color(&self, probability: f64) -> Color {
+        let (safe, bomb) = self.probability_colors;
+        let t = probability.clamp(0., 1.) as f32;
+        let [r, g, b, a] = safe.as_rgba_f32();
+        let [r2, g2, b2, a2] = bomb.as_rgba_f32();
+        Color::rgba(
+            r + (r2 - r) * t,
+            g + (g2 - g) * t,
+            b + (b2 - b) * t,
+            a + (a2 - a) * t,
+        )
+    }
+
     /// Safely retrieves the color matching a bomb counter
     pub fn bomb_counter_color(&self, counter: u8) -> Color {
         let counter = counter.saturating_sub(1) as usize;
@@ -1,6 +1,10 @@
 // board_options.rs
-use bevy::prelude::{Vec3, Resource};
+use crate::resources::Topology;
+use bevy::prelude::{Resource, Vec2, Vec3, Window};
 use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
 
 /// Tile size options
 #[derive(Debug, Clone, Serialize, Deserialize, Resource)]
@@ -11,6 +15,23 @@ pub enum TileSize {
     Adaptive { min: f32, max: f32 },
 }
 
+impl TileSize {
+    /// Computes the tile size, matching the `window` according to the size of a `topology` tile
+    /// map of `dimensions` if adaptive
+    pub fn size(&self, window: Option<&Window>, topology: Topology, dimensions: (u16, u16)) -> f32 {
+        match *self {
+            TileSize::Fixed(v) => v,
+            TileSize::Adaptive { min, max } => {
+                let window = window.expect("get window primary error");
+                let board_size = topology.board_size(dimensions, 1.);
+                let max_width = window.width() / board_size.x;
+                let max_heigth = window.height() / board_size.y;
+                max_width.min(max_heigth).clamp(min, max)
+            }
+        }
+    }
+}
+
 /// Board position customization options
 #[derive(Debug, Clone, Serialize, Deserialize, Resource)]
 pub enum BoardPosition {
@@ -20,6 +41,74 @@ pub enum BoardPosition {
     Custom(Vec3),
 }
 
+impl BoardPosition {
+    /// Board anchor position (bottom left) for a board of `board_size`
+    pub fn anchor(&self, board_size: Vec2) -> Vec3 {
+        match *self {
+            BoardPosition::Centered { offset } => {
+                Vec3::new(-(board_size.x / 2.), -(board_size.y / 2.), 0.) + offset
+            }
+            BoardPosition::Custom(p) => p,
+        }
+    }
+}
+
+/// Bomb placement options
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Resource)]
+pub enum BombPlacement {
+    /// Bombs are placed when the board is generated
+    #[default]
+    OnCreation,
+    /// Bombs are placed when the first tile is uncovered, which never holds a bomb
+    FirstClick {
+        /// Also keep the neighbors of the first tile free of bombs
+        safe_square: bool,
+    },
+}
+
+/// Board rendering options
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum RenderMode {
+    /// An entity per tile, with cover, flag, bomb and bomb counter child entities
+    #[default]
+    Sprites,
+    /// A mesh per square chunk of `size` tiles, textured from a generated tile atlas.
+    ///
+    /// Meant for huge boards, the `BoardAssets` textures, the probability overlay and the reveal
+    /// animation are not used. Hex boards fall back to sprites
+    Chunks { size: u16 },
+}
+
+/// Mouse input triggering a chord on an uncovered bomb neighbor tile
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ChordInput {
+    /// Click of a `BoardAction::Chord` button, the middle one by default
+    #[default]
+    MiddleClick,
+    /// `BoardAction::Reveal` and `BoardAction::Flag` buttons pressed together
+    BothButtons,
+    /// `BoardAction::Reveal` click on an uncovered tile, also applying to the cursor
+    LeftClickOnNumber,
+}
+
+/// Fallback of the no guess generation when every attempt needs guessing
+#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize)]
+pub enum NoGuessFallback {
+    /// Keeps the last generated board, which needs guessing
+    #[default]
+    KeepLast,
+    /// Removes a bomb and tries again with a new attempt budget
+    RemoveBomb,
+}
+
+/// No guess generation options
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct NoGuess {
+    /// Boards generated before falling back
+    pub max_attempts: u32,
+    pub fallback: NoGuessFallback,
+}
+
 /// Board generation options. Must be used as a resource
 // We use serde to allow saving option presets and loading them at runtime
 #[derive(Debug, Clone, Serialize, Deserialize, Resource)]
@@ -36,8 +125,145 @@ pub struct BoardOptions {
     pub tile_padding: f32,
     /// Does the board generate a safe place to start
     pub safe_start: bool,
+    /// Bomb placement seed, a random one is picked if not set
+    pub seed: Option<u64>,
+    /// When the bombs are placed
+    #[serde(default)]
+    pub bomb_placement: BombPlacement,
+    /// Only generate boards which can be solved from the opening tile without guessing.
+    ///
+    /// The opening is the first uncovered tile with `BombPlacement::FirstClick`, or the board
+    /// center, uncovered on creation, with `BombPlacement::OnCreation`
+    #[serde(default)]
+    pub no_guess: Option<NoGuess>,
+    /// Mouse input uncovering the neighbors of an uncovered tile with all its bombs flagged
+    #[serde(default)]
+    pub chord_input: ChordInput,
+    /// Allow undoing and redoing actions, won games are then kept out of the high scores
+    #[serde(default)]
+    pub undo: bool,
+    /// Delay between the rings of an uncovered region, which then ripples out from the revealed
+    /// tile. Cosmetic only, the game state is updated at once
+    #[serde(default)]
+    pub reveal_ring_delay: Option<Duration>,
+    /// Tile entities or chunk meshes
+    #[serde(default)]
+    pub render_mode: RenderMode,
+    /// Square, hex or wrapping square tiles
+    #[serde(default)]
+    pub topology: Topology,
 }
 
+/// Invalid board options
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BoardOptionsError {
+    /// Width or height out of `BoardOptions::MIN_MAP_SIZE..=max`, see `BoardOptions::max_map_size`
+    MapSize { max: u16 },
+    /// Bomb count out of `1..=max`
+    BombCount { max: u16 },
+}
+
+impl Display for BoardOptionsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MapSize { max } => write!(
+                f,
+                "Width and height must be between {} and {}",
+                BoardOptions::MIN_MAP_SIZE,
+                max
+            ),
+            Self::BombCount { max } => write!(f, "Mines must be between 1 and {}", max),
+        }
+    }
+}
+
+impl Error for BoardOptionsError {}
+
+impl BoardOptions {
+    pub const MIN_MAP_SIZE: u16 = 2;
+    pub const MAX_MAP_SIZE: u16 = 100;
+    /// Maximum width and height of `RenderMode::Chunks` boards, drawn without tile entities
+    pub const MAX_CHUNKED_MAP_SIZE: u16 = 1000;
+
+    /// 9x9 board with 10 bombs
+    pub fn beginner() -> Self {
+        Self {
+            map_size: (9, 9),
+            bomb_count: 10,
+            ..Default::default()
+        }
+    }
+
+    /// 16x16 board with 40 bombs
+    pub fn intermediate() -> Self {
+        Self {
+            map_size: (16, 16),
+            bomb_count: 40,
+            ..Default::default()
+        }
+    }
+
+    /// 30x16 board with 99 bombs
+    pub fn expert() -> Self {
+        Self {
+            map_size: (30, 16),
+            bomb_count: 99,
+            ..Default::default()
+        }
+    }
+
+    /// Custom board, validated by `BoardOptions::validate`
+    pub fn custom(
+        map_size: (u16, u16),
+        bomb_count: u16,
+        safe_start: bool,
+    ) -> Result<Self, BoardOptionsError> {
+        let options = Self {
+            map_size,
+            bomb_count,
+            safe_start,
+            ..Default::default()
+        };
+        options.validate()?;
+        Ok(options)
+    }
+
+    /// Maximum width and height: chunk rendered boards can be much larger than sprite ones.
+    ///
+    /// Hex boards are always rendered with sprites
+    pub fn max_map_size(&self) -> u16 {
+        match self.render_mode {
+            RenderMode::Chunks { .. } if self.topology != Topology::Hex => {
+                Self::MAX_CHUNKED_MAP_SIZE
+            }
+            _ => Self::MAX_MAP_SIZE,
+        }
+    }
+
+    /// Checks the map size and the bomb count.
+    ///
+    /// A safe start needs a tile and its neighbors free of bombs, other boards a single tile
+    pub fn validate(&self) -> Result<(), BoardOptionsError> {
+        let (width, height) = self.map_size;
+        let max_size = self.max_map_size();
+        let sizes = Self::MIN_MAP_SIZE..=max_size;
+        if !sizes.contains(&width) || !sizes.contains(&height) {
+            return Err(BoardOptionsError::MapSize { max: max_size });
+        }
+        let free = if self.safe_start {
+            1 + self.topology.neighbor_count() as u32
+        } else {
+            1
+        };
+        // Large boards hold more tiles than the bomb count can tell
+        let tiles = width as u32 * height as u32;
+        let max = tiles.saturating_sub(free).min(u16::MAX as u32) as u16;
+        if self.bomb_count == 0 || self.bomb_count > max {
+            return Err(BoardOptionsError::BombCount { max });
+        }
+        Ok(())
+    }
+}
 
 impl Default for TileSize {
     fn default() -> Self {
@@ -56,6 +282,15 @@ impl Default for BoardPosition {
     }
 }
 
+impl Default for NoGuess {
+    fn default() -> Self {
+        Self {
+            max_attempts: 100,
+            fallback: Default::default(),
+        }
+    }
+}
+
 impl Default for BoardOptions {
     fn default() -> Self {
         Self {
@@ -65,6 +300,14 @@ impl Default for BoardOptions {
             tile_size: Default::default(),
             tile_padding: 0.,
             safe_start: false,
+            seed: None,
+            bomb_placement: Default::default(),
+            no_guess: None,
+            chord_input: Default::default(),
+            undo: false,
+            reveal_ring_delay: None,
+            render_mode: Default::default(),
+            topology: Default::default(),
         }
     }
 }
\ No newline at end of file
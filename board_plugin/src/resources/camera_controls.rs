@@ -0,0 +1,26 @@
+// camera_controls.rs
+use bevy::prelude::Resource;
+
+/// Pan and zoom settings of the `BoardCamera`. Must be used as a resource
+#[derive(Debug, Clone, Resource)]
+pub struct CameraControls {
+    /// Pan speed of the pan keys, in window pixels per second
+    pub pan_speed: f32,
+    /// Scale factor of a zoom key press or a mouse wheel notch
+    pub zoom_step: f32,
+    /// Smallest projection scale, zooming in the most
+    pub min_scale: f32,
+    /// Largest projection scale, zooming out the most
+    pub max_scale: f32,
+}
+
+impl Default for CameraControls {
+    fn default() -> Self {
+        Self {
+            pan_speed: 500.,
+            zoom_step: 1.2,
+            min_scale: 0.1,
+            max_scale: 10.,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+// game_status.rs
+use bevy::prelude::Resource;
+
+/// Status of the current game. Must be used as a resource
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Resource)]
+pub enum GameStatus {
+    /// The board is generated and no tile is uncovered yet
+    #[default]
+    Ready,
+    /// Tiles are being uncovered
+    Playing,
+    /// Every safe tile was uncovered
+    Won,
+    /// A bomb was uncovered
+    Lost,
+}
+
+impl GameStatus {
+    /// Is the game won or lost
+    pub const fn is_over(&self) -> bool {
+        matches!(self, Self::Won | Self::Lost)
+    }
+}
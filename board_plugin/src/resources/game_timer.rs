@@ -0,0 +1,12 @@
+// game_timer.rs
+use bevy::prelude::Resource;
+use std::time::Duration;
+
+/// Time spent on the current game. Must be used as a resource
+#[derive(Debug, Default, Clone, Resource)]
+pub struct GameTimer {
+    /// Time played since the first move, a safe start not being one
+    pub elapsed: Duration,
+    /// Is the timer counting
+    pub running: bool,
+}
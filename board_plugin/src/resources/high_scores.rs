@@ -0,0 +1,123 @@
+// high_scores.rs
+use crate::resources::{ron_file, BoardOptions, BombPlacement, Topology};
+use bevy::log;
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Entries kept in each high score table
+const MAX_ENTRIES: usize = 10;
+
+/// High score file name, in the `HighScores` directory
+const FILE_NAME: &str = "high_scores.ron";
+
+/// Board generation options sharing a high score table
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ScoreKey {
+    pub map_size: (u16, u16),
+    pub bomb_count: u16,
+    pub safe_start: bool,
+    pub bomb_placement: BombPlacement,
+    pub no_guess: bool,
+    #[serde(default)]
+    pub topology: Topology,
+}
+
+impl From<&BoardOptions> for ScoreKey {
+    fn from(options: &BoardOptions) -> Self {
+        Self {
+            map_size: options.map_size,
+            bomb_count: options.bomb_count,
+            safe_start: options.safe_start,
+            bomb_placement: options.bomb_placement,
+            no_guess: options.no_guess.is_some(),
+            topology: options.topology,
+        }
+    }
+}
+
+/// A won game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighScore {
+    pub time: Duration,
+    /// Seconds since the Unix epoch
+    pub date: u64,
+    pub player: String,
+    pub seed: u64,
+}
+
+/// Fastest games of a `ScoreKey`, fastest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreTable {
+    pub key: ScoreKey,
+    pub entries: Vec<HighScore>,
+}
+
+/// Name recorded with the high scores. Must be used as a resource
+#[derive(Debug, Clone, Resource)]
+pub struct PlayerName(pub String);
+
+/// Fastest times for each board options, saved in a directory. Must be used as a resource
+/// to record the won games
+#[derive(Debug, Clone, Default, Resource)]
+pub struct HighScores {
+    directory: PathBuf,
+    tables: Vec<ScoreTable>,
+}
+
+impl HighScores {
+    /// Loads the high scores saved in `directory`, starting empty if there are none
+    pub fn load(directory: impl Into<PathBuf>) -> Self {
+        let directory = directory.into();
+        let path = directory.join(FILE_NAME);
+        let tables = match ron_file::load(&path) {
+            Ok(tables) => tables,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => {
+                log::error!("Failed to load {}: {}", path.display(), e);
+                Vec::new()
+            }
+        };
+        Self { directory, tables }
+    }
+
+    /// Saves the high scores in their directory
+    pub fn save(&self) -> io::Result<()> {
+        ron_file::save(&self.tables, self.directory.join(FILE_NAME))
+    }
+
+    /// Adds `score` to the table of `key` if it is fast enough, returning its rank
+    pub fn record(&mut self, key: ScoreKey, score: HighScore) -> Option<usize> {
+        let table = match self.tables.iter().position(|t| t.key == key) {
+            Some(i) => &mut self.tables[i],
+            None => {
+                self.tables.push(ScoreTable {
+                    key,
+                    entries: Vec::new(),
+                });
+                self.tables.last_mut()?
+            }
+        };
+        let rank = table.entries.partition_point(|e| e.time <= score.time);
+        if rank >= MAX_ENTRIES {
+            return None;
+        }
+        table.entries.insert(rank, score);
+        table.entries.truncate(MAX_ENTRIES);
+        Some(rank)
+    }
+
+    /// Entries of the table of `key`, fastest first
+    pub fn table(&self, key: &ScoreKey) -> &[HighScore] {
+        self.tables
+            .iter()
+            .find(|t| &t.key == key)
+            .map_or(&[], |t| &t.entries)
+    }
+
+    pub fn tables(&self) -> &[ScoreTable] {
+        &self.tables
+    }
+}
@@ -0,0 +1,216 @@
+// input_bindings.rs
+use crate::resources::ron_file;
+use bevy::prelude::{GamepadButtonType, KeyCode, MouseButton, Resource};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+/// Board action triggered by the player input
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum BoardAction {
+    /// Sends a `TileTriggerEvent`
+    Reveal,
+    /// Sends a `TileMarkEvent`
+    Flag,
+    /// Sends a `TileChordEvent`, see `ChordInput` for the mouse
+    Chord,
+    /// Sends a `RestartBoardEvent`
+    Restart,
+    /// Sends a `HintEvent`
+    Hint,
+    /// Moves the cursor, the gamepad left stick always does
+    CursorUp,
+    CursorDown,
+    CursorLeft,
+    CursorRight,
+    /// Drags the `BoardCamera` view along with the mouse moves while held, Shift by default
+    Pan,
+    /// Pans the `BoardCamera` while held
+    PanUp,
+    PanDown,
+    PanLeft,
+    PanRight,
+    /// Zooms the `BoardCamera`, the mouse wheel always does
+    ZoomIn,
+    ZoomOut,
+}
+
+/// Input bound to a board action
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Binding {
+    /// Mouse button, acting on the hovered tile
+    Mouse(MouseButton),
+    /// Keyboard key, acting on the cursor tile
+    Key(KeyCode),
+    /// Button of any gamepad, acting on the cursor tile
+    Gamepad(GamepadButtonType),
+}
+
+/// Inputs bound to each board action. Must be used as a resource
+#[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+pub struct InputBindings {
+    pub actions: BTreeMap<BoardAction, Vec<Binding>>,
+}
+
+impl InputBindings {
+    /// Reads the bindings from the RON file at `path`, actions missing from the file keep
+    /// their default bindings
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut bindings: Self = ron_file::load(path)?;
+        // Files saved before some actions existed
+        for (action, defaults) in Self::default().actions {
+            bindings.actions.entry(action).or_insert(defaults);
+        }
+        Ok(bindings)
+    }
+
+    /// Writes the bindings as a RON file at `path`, creating its directory
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        ron_file::save(self, path)
+    }
+
+    /// Inputs bound to `action`
+    pub fn bindings(&self, action: BoardAction) -> &[Binding] {
+        self.actions.get(&action).map_or(&[], |b| b.as_slice())
+    }
+
+    /// Is `binding` bound to `action`
+    pub fn is_bound(&self, action: BoardAction, binding: Binding) -> bool {
+        self.bindings(action).contains(&binding)
+    }
+
+    /// Actions bound to `binding`
+    pub fn actions_of(&self, binding: Binding) -> impl Iterator<Item = BoardAction> + '_ {
+        self.actions
+            .iter()
+            .filter(move |(_, bindings)| bindings.contains(&binding))
+            .map(|(action, _)| *action)
+    }
+
+    /// Mouse buttons bound to `action`
+    pub fn mouse_buttons(&self, action: BoardAction) -> impl Iterator<Item = MouseButton> + '_ {
+        self.bindings(action).iter().filter_map(|b| match b {
+            Binding::Mouse(button) => Some(*button),
+            _ => None,
+        })
+    }
+
+    /// Keys bound to `action`
+    pub fn keys(&self, action: BoardAction) -> impl Iterator<Item = KeyCode> + '_ {
+        self.bindings(action).iter().filter_map(|b| match b {
+            Binding::Key(key) => Some(*key),
+            _ => None,
+        })
+    }
+
+    /// Gamepad buttons bound to `action`
+    pub fn gamepad_buttons(
+        &self,
+        action: BoardAction,
+    ) -> impl Iterator<Item = GamepadButtonType> + '_ {
+        self.bindings(action).iter().filter_map(|b| match b {
+            Binding::Gamepad(button) => Some(*button),
+            _ => None,
+        })
+    }
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use Binding::*;
+        let actions = [
+            (
+                BoardAction::Reveal,
+                vec![
+                    Mouse(MouseButton::Left),
+                    Key(KeyCode::Space),
+                    Gamepad(GamepadButtonType::South),
+                ],
+            ),
+            (
+                BoardAction::Flag,
+                vec![
+                    Mouse(MouseButton::Right),
+                    Key(KeyCode::F),
+                    Gamepad(GamepadButtonType::East),
+                ],
+            ),
+            (
+                BoardAction::Chord,
+                vec![
+                    Mouse(MouseButton::Middle),
+                    Key(KeyCode::E),
+                    Gamepad(GamepadButtonType::West),
+                ],
+            ),
+            (
+                BoardAction::Restart,
+                vec![Key(KeyCode::R), Gamepad(GamepadButtonType::Start)],
+            ),
+            (
+                BoardAction::Hint,
+                vec![Key(KeyCode::T), Gamepad(GamepadButtonType::North)],
+            ),
+            (
+                BoardAction::CursorUp,
+                vec![
+                    Key(KeyCode::Up),
+                    Key(KeyCode::W),
+                    Gamepad(GamepadButtonType::DPadUp),
+                ],
+            ),
+            (
+                BoardAction::CursorDown,
+                vec![
+                    Key(KeyCode::Down),
+                    Key(KeyCode::S),
+                    Gamepad(GamepadButtonType::DPadDown),
+                ],
+            ),
+            (
+                BoardAction::CursorLeft,
+                vec![
+                    Key(KeyCode::Left),
+                    Key(KeyCode::A),
+                    Gamepad(GamepadButtonType::DPadLeft),
+                ],
+            ),
+            (
+                BoardAction::CursorRight,
+                vec![
+                    Key(KeyCode::Right),
+                    Key(KeyCode::D),
+                    Gamepad(GamepadButtonType::DPadRight),
+                ],
+            ),
+            (
+                BoardAction::Pan,
+                vec![Key(KeyCode::LShift), Key(KeyCode::RShift)],
+            ),
+            (BoardAction::PanUp, vec![Key(KeyCode::I)]),
+            (BoardAction::PanDown, vec![Key(KeyCode::K)]),
+            (BoardAction::PanLeft, vec![Key(KeyCode::J)]),
+            (BoardAction::PanRight, vec![Key(KeyCode::L)]),
+            (
+                BoardAction::ZoomIn,
+                vec![
+                    Key(KeyCode::Equals),
+                    Key(KeyCode::NumpadAdd),
+                    Gamepad(GamepadButtonType::RightTrigger),
+                ],
+            ),
+            (
+                BoardAction::ZoomOut,
+                vec![
+                    Key(KeyCode::Minus),
+                    Key(KeyCode::NumpadSubtract),
+                    Gamepad(GamepadButtonType::LeftTrigger),
+                ],
+            ),
+        ];
+        Self {
+            actions: actions.into_iter().collect(),
+        }
+    }
+}
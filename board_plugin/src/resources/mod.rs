@@ -2,11 +2,35 @@
 
 pub(crate) mod tile;
 pub(crate) mod tile_map;
+pub(crate) mod tile_set;
 pub(crate) mod board;
+pub(crate) mod topology;
+pub mod ron_file;
 
 mod board_options;
 mod board_assets;
+mod camera_controls;
+mod game_status;
+mod input_bindings;
+mod game_timer;
+mod high_scores;
+mod replay;
+mod saved_game;
+mod probability_overlay;
 
+pub use board::Board;
 pub use board_options::*;
+pub use tile::Tile;
+pub use tile_map::TileMap;
+pub use tile_set::TileSet;
+pub use topology::{Topology, HEX_TILE_IMAGE_HANDLE};
 
-pub use board_assets::*;
\ No newline at end of file
+pub use board_assets::*;
+pub use camera_controls::*;
+pub use game_status::*;
+pub use input_bindings::*;
+pub use game_timer::*;
+pub use high_scores::*;
+pub use replay::*;
+pub use saved_game::*;
+pub use probability_overlay::*;
\ No newline at end of file
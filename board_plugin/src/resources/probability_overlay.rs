@@ -0,0 +1,15 @@
+// probability_overlay.rs
+use bevy::prelude::Resource;
+
+/// Colors the covered tiles according to their bomb probability when enabled. Must be used as a resource.
+///
+/// Probabilities are only computed while enabled. They are exact, except when a group of linked
+/// frontier tiles (covered tiles next to uncovered bomb neighbors) has more than
+/// [`MAX_ENUMERATED_TILES`](crate::solver::MAX_ENUMERATED_TILES) tiles: its tiles are then
+/// given the probability of the tiles away from the frontier, which is only an approximation.
+/// Boards needing more than [`SOLVE_NODE_BUDGET`](crate::solver::SOLVE_NODE_BUDGET) search
+/// nodes fall back to the plain bomb density of the unknown tiles
+#[derive(Debug, Default, Clone, Resource)]
+pub struct ProbabilityOverlay {
+    pub enabled: bool,
+}
@@ -0,0 +1,187 @@
+// replay.rs
+use crate::components::Coordinates;
+use crate::game::Game;
+use crate::resources::{ron_file, BoardOptions};
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// Player action on a tile
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ReplayAction {
+    /// Sent as a `TileTriggerEvent`
+    Reveal,
+    /// Sent as a `TileMarkEvent`
+    Flag,
+    /// Sent as a `TileChordEvent`
+    Chord,
+    /// Sent as an `UndoEvent`
+    Undo,
+    /// Sent as a `RedoEvent`
+    Redo,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RecordedAction {
+    /// Time since the board was spawned
+    pub time: Duration,
+    pub action: ReplayAction,
+    /// Tile of the action, unused by undo and redo
+    pub coords: Coordinates,
+}
+
+/// Recorded board, written by `SaveReplayEvent` and played by `PlayReplayEvent`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    /// Game as it was spawned, holding the bomb layout or the seed of pending bombs
+    pub game: Game,
+    /// Options the board was generated and rendered with
+    pub options: BoardOptions,
+    /// Actions in recording order
+    pub actions: Vec<RecordedAction>,
+}
+
+/// Actions of the current board, recorded by the board plugin. Must be used as a resource
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ReplayRecorder {
+    pub(crate) initial: Option<Game>,
+    pub(crate) elapsed: Duration,
+    pub(crate) actions: Vec<RecordedAction>,
+}
+
+/// Replay being played, the player input is ignored meanwhile. Must be used as a resource
+#[derive(Debug, Clone, Resource)]
+pub struct ReplayPlayback {
+    pub(crate) replay: Replay,
+    pub(crate) time: Duration,
+    /// Index of the next action to send
+    pub(crate) next: usize,
+    /// Is the board being rebuilt, actions wait for the new one
+    pub(crate) rebuilding: bool,
+    pub paused: bool,
+    /// Playback speed factor
+    pub speed: f32,
+}
+
+impl Replay {
+    /// Reads a replay from the RON file at `path`
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        ron_file::load(path)
+    }
+
+    /// Writes the replay as a RON file at `path`, creating its directory
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        ron_file::save(self, path)
+    }
+
+    /// Time of the last action
+    pub fn duration(&self) -> Duration {
+        self.actions.last().map_or(Duration::ZERO, |a| a.time)
+    }
+
+    /// The game after the start tile and every action until `time`
+    pub fn game_at(&self, time: Duration) -> Game {
+        let mut game = self.game.clone();
+        game.set_undo(self.options.undo);
+        if let Some(coords) = game.start_tile(self.options.safe_start) {
+            game.reveal(coords);
+        }
+        for action in self.actions.iter().take_while(|a| a.time <= time) {
+            match action.action {
+                ReplayAction::Reveal => {
+                    game.reveal(action.coords);
+                }
+                ReplayAction::Flag => {
+                    game.toggle_flag(action.coords);
+                }
+                ReplayAction::Chord => {
+                    game.chord(action.coords);
+                }
+                ReplayAction::Undo if self.options.undo => {
+                    game.undo();
+                }
+                ReplayAction::Redo if self.options.undo => {
+                    game.redo();
+                }
+                ReplayAction::Undo | ReplayAction::Redo => (),
+            }
+        }
+        game
+    }
+}
+
+impl ReplayRecorder {
+    /// Replay of the current board, if it was spawned
+    pub fn replay(&self, options: BoardOptions) -> Option<Replay> {
+        Some(Replay {
+            game: self.initial.clone()?,
+            options,
+            actions: self.actions.clone(),
+        })
+    }
+
+    /// Forgets the recorded actions and starts recording `game`
+    pub(crate) fn start(&mut self, game: Game) {
+        self.initial = Some(game);
+        self.elapsed = Duration::ZERO;
+        self.actions.clear();
+    }
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            time: Duration::ZERO,
+            next: 0,
+            rebuilding: false,
+            paused: false,
+            speed: 1.,
+        }
+    }
+
+    /// Playback position
+    pub fn time(&self) -> Duration {
+        self.time
+    }
+
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Have all the actions been sent
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.replay.actions.len()
+    }
+
+    /// Moves to `time`, returning the game at this time to rebuild the board with
+    pub(crate) fn seek(&mut self, time: Duration) -> Game {
+        let time = time.min(self.replay.duration());
+        self.time = time;
+        self.next = self.replay.actions.partition_point(|a| a.time <= time);
+        self.rebuilding = true;
+        self.replay.game_at(time)
+    }
+
+    /// Advances the playback by `delta` real time, returning the actions to send now.
+    ///
+    /// Actions of different kinds are handled by different systems, so a call only returns
+    /// actions of a single kind to keep them in order
+    pub(crate) fn advance(&mut self, delta: Duration) -> &[RecordedAction] {
+        if self.paused || self.rebuilding {
+            return &[];
+        }
+        self.time += delta.mul_f32(self.speed.max(0.));
+        let start = self.next;
+        let actions = &self.replay.actions[start..];
+        let kind = actions.first().map(|a| a.action);
+        let count = actions
+            .iter()
+            .take_while(|a| a.time <= self.time && Some(a.action) == kind)
+            .count();
+        self.next += count;
+        &self.replay.actions[start..start + count]
+    }
+}
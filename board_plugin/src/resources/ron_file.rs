@@ -0,0 +1,23 @@
+// ron_file.rs
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Reads a value from the RON file at `path`
+pub fn load<T: DeserializeOwned>(path: impl AsRef<Path>) -> io::Result<T> {
+    let content = fs::read_to_string(path)?;
+    ron::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Writes `value` as a RON file at `path`, creating its directory
+pub fn save<T: Serialize>(value: &T, path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+    let content = ron::ser::to_string_pretty(value, Default::default())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory)?;
+    }
+    fs::write(path, content)
+}
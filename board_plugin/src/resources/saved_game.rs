@@ -0,0 +1,31 @@
+// saved_game.rs
+use crate::game::Game;
+use crate::resources::{ron_file, BoardOptions};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// Game in progress, written by `SaveBoardEvent` and read by `LoadBoardEvent`.
+///
+/// The game holds the tile map, the covered and flagged tiles and the seed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub game: Game,
+    /// Options the board was generated and rendered with
+    pub options: BoardOptions,
+    /// Time spent on the game
+    pub elapsed: Duration,
+}
+
+impl SavedGame {
+    /// Reads a saved game from the RON file at `path`
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        ron_file::load(path)
+    }
+
+    /// Writes the game as a RON file at `path`, creating its directory
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        ron_file::save(self, path)
+    }
+}
@@ -1,9 +1,10 @@
 // tile.rs
 #[cfg(feature = "debug")]
 use colored::Colorize;
+use serde::{Deserialize, Serialize};
 
 /// Enum describing a Minesweeper tile
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Tile {
     /// Is a bomb
     Bomb,
@@ -1,50 +1,38 @@
 // tile_map.rs
 use crate::resources::tile::Tile;
-use std::ops::{Deref, DerefMut};
 use crate::components::Coordinates;
-use rand::{thread_rng, Rng};
-
-/// Delta coordinates for all 8 square neighbors
-const SQUARE_COORDINATES: [(i8, i8); 8] = [
-    // Bottom left
-    (-1, -1),
-    // Bottom
-    (0, -1),
-    // Bottom right
-    (1, -1),
-    // Left
-    (-1, 0),
-    // Right
-    (1, 0),
-    // Top Left
-    (-1, 1),
-    // Top
-    (0, 1),
-    // Top right
-    (1, 1),
-];
-
-/// Base tile map
-#[derive(Debug, Clone)]
+use crate::resources::Topology;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+/// Base tile map, stored line by line from the bottom.
+///
+/// A tile index is `y * width + x`
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TileMap {
     bomb_count: u16,
     height: u16,
     width: u16,
-    map: Vec<Vec<Tile>>,
+    tiles: Vec<Tile>,
+    #[serde(default)]
+    topology: Topology,
 }
 
 impl TileMap {
-    /// Generates an empty map
+    /// Generates an empty map of square tiles
     pub fn empty(width: u16, height: u16) -> Self {
-        let map = (0..height)
-            .into_iter()
-            .map(|_| (0..width).into_iter().map(|_| Tile::Empty).collect())
-            .collect();
+        Self::with_topology(width, height, Topology::Square)
+    }
+
+    /// Generates an empty map of `topology` tiles
+    pub fn with_topology(width: u16, height: u16, topology: Topology) -> Self {
         Self {
             bomb_count: 0,
             height,
             width,
-            map,
+            tiles: vec![Tile::Empty; width as usize * height as usize],
+            topology,
         }
     }
 
@@ -54,9 +42,9 @@ impl TileMap {
             "Map ({}, {}) with {} bombs:\n",
             self.width, self.height, self.bomb_count
         );
-        let line: String = (0..=(self.width + 1)).into_iter().map(|_| '-').collect();
+        let line: String = (0..=(self.width + 1)).map(|_| '-').collect();
         buffer = format!("{}{}\n", buffer, line);
-        for line in self.iter().rev() {
+        for line in self.rows().rev() {
             buffer = format!("{}|", buffer);
             for tile in line.iter() {
                 buffer = format!("{}{}", buffer, tile.console_output());
@@ -76,23 +64,105 @@ impl TileMap {
         self.height
     }
 
+    // Getter for `topology`
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
     // Getter for `bomb_count`
     pub fn bomb_count(&self) -> u16 {
         self.bomb_count
     }
 
-    pub fn safe_square_at(&self, coordinates: Coordinates) -> impl Iterator<Item = Coordinates> {
-        SQUARE_COORDINATES
+    /// Number of tiles
+    pub fn tile_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Index of the tile at `coordinates`, if it is inside the map
+    pub fn index(&self, coordinates: Coordinates) -> Option<usize> {
+        (coordinates.x < self.width && coordinates.y < self.height)
+            .then(|| coordinates.y as usize * self.width as usize + coordinates.x as usize)
+    }
+
+    /// Coordinates of the tile at `index`
+    pub fn coordinates(&self, index: usize) -> Coordinates {
+        Coordinates {
+            x: (index % self.width as usize) as u16,
+            y: (index / self.width as usize) as u16,
+        }
+    }
+
+    /// Tiles, by index
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    /// Lines of tiles, from the bottom
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[Tile]> {
+        self.tiles.chunks(self.width.max(1) as usize)
+    }
+
+    /// Tiles with their coordinates, line by line from the bottom
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinates, &Tile)> {
+        self.tiles
             .iter()
-            .copied()
-            .map(move |tuple| coordinates + tuple)
+            .enumerate()
+            .map(|(i, tile)| (self.coordinates(i), tile))
+    }
+
+    /// Neighbors of the tile at `coordinates` inside the map, according to the map topology.
+    ///
+    /// On wrapping maps less than 3 tiles wide or high, a neighbor reached across both edges is
+    /// only listed once, and the tile is never its own neighbor
+    pub fn safe_square_at(
+        &self,
+        coordinates: Coordinates,
+    ) -> impl Iterator<Item = Coordinates> + '_ {
+        let offsets = self.topology.neighbor_offsets(coordinates);
+        offsets.iter().enumerate().filter_map(move |(i, offset)| {
+            let neighbor = self.neighbor(coordinates, *offset)?;
+            let repeated = self.topology == Topology::Torus
+                && (neighbor == coordinates
+                    || offsets[..i]
+                        .iter()
+                        .any(|o| self.neighbor(coordinates, *o) == Some(neighbor)));
+            (!repeated).then_some(neighbor)
+        })
+    }
+
+    /// Tile at `offset` from `coordinates`, across the edges of wrapping maps
+    fn neighbor(&self, coordinates: Coordinates, (x, y): (i8, i8)) -> Option<Coordinates> {
+        match self.topology {
+            Topology::Torus => {
+                let wrap = |c: u16, delta: i8, size: u16| {
+                    (c as i32 + delta as i32).checked_rem_euclid(size as i32)
+                };
+                Some(Coordinates {
+                    x: wrap(coordinates.x, x, self.width)? as u16,
+                    y: wrap(coordinates.y, y, self.height)? as u16,
+                })
+            }
+            Topology::Square | Topology::Hex => coordinates
+                .checked_add((x, y))
+                .filter(|c| self.index(*c).is_some()),
+        }
+    }
+
+    /// Indexes of the neighbors of the tile at `index` inside the map
+    pub fn neighbor_indexes(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let coordinates = self.coordinates(index);
+        self.safe_square_at(coordinates)
+            .filter_map(|c| self.index(c))
+    }
+
+    /// Retrieves the tile at `coordinates`, if it is inside the map
+    pub fn tile_at(&self, coordinates: Coordinates) -> Option<&Tile> {
+        self.tiles.get(self.index(coordinates)?)
     }
 
     pub fn is_bomb_at(&self, coordinates: Coordinates) -> bool {
-        if coordinates.x >= self.width || coordinates.y >= self.height {
-            return false;
-        };
-        self.map[coordinates.y as usize][coordinates.x as usize].is_bomb()
+        self.tile_at(coordinates).is_some_and(|t| t.is_bomb())
     }
     
     pub fn bomb_count_at(&self, coordinates: Coordinates) -> u8 {
@@ -106,50 +176,69 @@ impl TileMap {
         res as u8
     }
 
-    /// Places bombs and bomb neighbor tiles
-    pub fn set_bombs(&mut self, bomb_count: u16) {
+    /// Places bombs and bomb neighbor tiles.
+    ///
+    /// The placement only depends on `seed`, so the same seed always gives the same map
+    pub fn set_bombs(&mut self, bomb_count: u16, seed: u64) {
+        self.set_bombs_avoiding(bomb_count, seed, &[]);
+    }
+
+    /// Places bombs and bomb neighbor tiles, never placing a bomb on the `safe` coordinates.
+    ///
+    /// The bomb count is capped to the number of tiles available for bombs
+    pub fn set_bombs_avoiding(&mut self, bomb_count: u16, seed: u64, safe: &[Coordinates]) {
+        let safe_tiles = safe
+            .iter()
+            .filter(|c| c.x < self.width && c.y < self.height)
+            .count();
+        let available = (self.width as usize * self.height as usize).saturating_sub(safe_tiles);
+        let bomb_count = bomb_count.min(available.min(u16::MAX as usize) as u16);
         self.bomb_count = bomb_count;
         let mut remaining_bombs = bomb_count;
-        let mut rng = thread_rng();
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
         // Place bombs
         while remaining_bombs > 0 {
-            let (x, y) = (
-                rng.gen_range(0..self.width) as usize,
-                rng.gen_range(0..self.height) as usize,
-            );
-            if let Tile::Empty = self[y][x] {
-                self[y][x] = Tile::Bomb;
-                remaining_bombs -= 1;
+            let coords = Coordinates {
+                x: rng.gen_range(0..self.width),
+                y: rng.gen_range(0..self.height),
+            };
+            if safe.contains(&coords) {
+                continue;
             }
-        }
-        // Place bomb neighbors
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let coords = Coordinates { x, y };
-                if self.is_bomb_at(coords) {
-                    continue;
-                }
-                let num = self.bomb_count_at(coords);
-                if num == 0 {
-                    continue;
-                }
-                let tile = &mut self[y as usize][x as usize];
-                *tile = Tile::BombNeighbor(num);
+            let index = self.index(coords).expect("random coordinates inside the map");
+            if let Tile::Empty = self.tiles[index] {
+                self.tiles[index] = Tile::Bomb;
+                remaining_bombs -= 1;
             }
         }
+        self.set_bomb_neighbors();
     }
-}
 
-impl Deref for TileMap {
-    type Target = Vec<Vec<Tile>>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.map
+    /// Places bombs on the given coordinates, and the bomb neighbor tiles
+    pub fn set_bombs_at(&mut self, bombs: &[Coordinates]) {
+        for coords in bombs {
+            if let Some(index) = self.index(*coords) {
+                self.tiles[index] = Tile::Bomb;
+            }
+        }
+        self.bomb_count = self.tiles.iter().filter(|t| t.is_bomb()).count() as u16;
+        self.set_bomb_neighbors();
     }
-}
 
-impl DerefMut for TileMap {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.map
+    /// Places bomb neighbor tiles, and empty tiles away from the bombs
+    fn set_bomb_neighbors(&mut self) {
+        for index in 0..self.tiles.len() {
+            if self.tiles[index].is_bomb() {
+                continue;
+            }
+            let num = self
+                .neighbor_indexes(index)
+                .filter(|i| self.tiles[*i].is_bomb())
+                .count() as u8;
+            self.tiles[index] = match num {
+                0 => Tile::Empty,
+                _ => Tile::BombNeighbor(num),
+            };
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,126 @@
+// tile_set.rs
+use crate::components::Coordinates;
+use serde::{Deserialize, Serialize};
+
+/// Bits per block
+const BLOCK_BITS: usize = u64::BITS as usize;
+
+/// Set of the tiles of a `width` x `height` map, stored as a bitset over the tile indexes.
+///
+/// A tile index is `y * width + x`, like the `TileMap` indexes
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TileSet {
+    width: u16,
+    height: u16,
+    len: usize,
+    blocks: Vec<u64>,
+}
+
+impl TileSet {
+    /// Set without any tile
+    pub fn empty(width: u16, height: u16) -> Self {
+        let tiles = width as usize * height as usize;
+        Self {
+            width,
+            height,
+            len: 0,
+            blocks: vec![0; tiles.div_ceil(BLOCK_BITS)],
+        }
+    }
+
+    /// Set of every tile
+    pub fn full(width: u16, height: u16) -> Self {
+        let mut set = Self::empty(width, height);
+        let tiles = width as usize * height as usize;
+        set.blocks.fill(u64::MAX);
+        if !tiles.is_multiple_of(BLOCK_BITS) {
+            if let Some(last) = set.blocks.last_mut() {
+                *last = (1 << (tiles % BLOCK_BITS)) - 1;
+            }
+        }
+        set.len = tiles;
+        set
+    }
+
+    /// Index of `coords`, if it is inside the map
+    pub fn index(&self, coords: Coordinates) -> Option<usize> {
+        (coords.x < self.width && coords.y < self.height)
+            .then(|| coords.y as usize * self.width as usize + coords.x as usize)
+    }
+
+    /// Coordinates of the tile at `index`
+    pub fn coordinates(&self, index: usize) -> Coordinates {
+        Coordinates {
+            x: (index % self.width as usize) as u16,
+            y: (index / self.width as usize) as u16,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn contains(&self, coords: Coordinates) -> bool {
+        self.index(coords).is_some_and(|i| self.contains_index(i))
+    }
+
+    pub fn contains_index(&self, index: usize) -> bool {
+        self.blocks
+            .get(index / BLOCK_BITS)
+            .is_some_and(|block| block & (1 << (index % BLOCK_BITS)) != 0)
+    }
+
+    /// Adds `coords`, returning whether it was missing
+    pub fn insert(&mut self, coords: Coordinates) -> bool {
+        self.index(coords).is_some_and(|i| self.insert_index(i))
+    }
+
+    /// Adds the tile at `index`, returning whether it was missing
+    pub fn insert_index(&mut self, index: usize) -> bool {
+        if index >= self.width as usize * self.height as usize || self.contains_index(index) {
+            return false;
+        }
+        self.blocks[index / BLOCK_BITS] |= 1 << (index % BLOCK_BITS);
+        self.len += 1;
+        true
+    }
+
+    /// Removes `coords`, returning whether it was present
+    pub fn remove(&mut self, coords: Coordinates) -> bool {
+        self.index(coords).is_some_and(|i| self.remove_index(i))
+    }
+
+    /// Removes the tile at `index`, returning whether it was present
+    pub fn remove_index(&mut self, index: usize) -> bool {
+        if !self.contains_index(index) {
+            return false;
+        }
+        self.blocks[index / BLOCK_BITS] &= !(1 << (index % BLOCK_BITS));
+        self.len -= 1;
+        true
+    }
+
+    /// Indexes of the tiles, in increasing order
+    pub fn indexes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.blocks.iter().enumerate().flat_map(|(i, block)| {
+            let mut block = *block;
+            std::iter::from_fn(move || {
+                if block == 0 {
+                    return None;
+                }
+                let bit = block.trailing_zeros() as usize;
+                block &= block - 1;
+                Some(i * BLOCK_BITS + bit)
+            })
+        })
+    }
+
+    /// Coordinates of the tiles, line by line from the bottom
+    pub fn iter(&self) -> impl Iterator<Item = Coordinates> + '_ {
+        self.indexes().map(|i| self.coordinates(i))
+    }
+}
@@ -0,0 +1,214 @@
+// topology.rs
+use crate::components::Coordinates;
+use crate::resources::SpriteMaterial;
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use serde::{Deserialize, Serialize};
+
+/// Delta coordinates for all 8 square neighbors
+const SQUARE_COORDINATES: [(i8, i8); 8] = [
+    // Bottom left
+    (-1, -1),
+    // Bottom
+    (0, -1),
+    // Bottom right
+    (1, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top Left
+    (-1, 1),
+    // Top
+    (0, 1),
+    // Top right
+    (1, 1),
+];
+
+/// Delta coordinates for the 6 hex neighbors of a tile on an even line
+const HEX_EVEN_COORDINATES: [(i8, i8); 6] = [
+    // Bottom left
+    (-1, -1),
+    // Bottom right
+    (0, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top left
+    (-1, 1),
+    // Top right
+    (0, 1),
+];
+
+/// Delta coordinates for the 6 hex neighbors of a tile on an odd line, shifted right
+const HEX_ODD_COORDINATES: [(i8, i8); 6] = [
+    // Bottom left
+    (0, -1),
+    // Bottom right
+    (1, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top left
+    (0, 1),
+    // Top right
+    (1, 1),
+];
+
+/// Hexagon mask of the hex tile sprites without their own texture
+pub const HEX_TILE_IMAGE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 0x6d5e_2f1c_84a3_b907);
+
+/// Hexagon height for a width of 1
+const HEX_HEIGHT: f32 = 1.154_700_5;
+/// Distance between two hex lines for a width of 1
+const HEX_LINE_STEP: f32 = 0.866_025_4;
+
+/// Tile shapes and neighbors
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Topology {
+    /// Square tiles with 8 neighbors
+    #[default]
+    Square,
+    /// Pointy top hexagon tiles with 6 neighbors, odd lines being shifted right by half a tile.
+    ///
+    /// The tile size is the hexagon width
+    Hex,
+    /// Square tiles with 8 neighbors, wrapping around the board edges: the left edge tiles
+    /// neighbor the right edge ones, and the bottom edge tiles the top edge ones
+    Torus,
+}
+
+impl Topology {
+    /// Delta coordinates of the neighbors of the tile at `coordinates`
+    pub(crate) fn neighbor_offsets(self, coordinates: Coordinates) -> &'static [(i8, i8)] {
+        match self {
+            Topology::Square | Topology::Torus => &SQUARE_COORDINATES,
+            Topology::Hex if coordinates.y.is_multiple_of(2) => &HEX_EVEN_COORDINATES,
+            Topology::Hex => &HEX_ODD_COORDINATES,
+        }
+    }
+
+    /// Maximum neighbor count of a tile
+    pub fn neighbor_count(self) -> usize {
+        match self {
+            Topology::Square | Topology::Torus => SQUARE_COORDINATES.len(),
+            Topology::Hex => HEX_EVEN_COORDINATES.len(),
+        }
+    }
+
+    /// Center of the tile at `coordinates` from the board anchor (bottom left)
+    pub fn tile_center(self, coordinates: Coordinates, tile_size: f32) -> Vec2 {
+        let (x, y) = (coordinates.x as f32, coordinates.y as f32);
+        match self {
+            Topology::Square | Topology::Torus => Vec2::new(x + 0.5, y + 0.5) * tile_size,
+            Topology::Hex => {
+                let shift = if coordinates.y.is_multiple_of(2) { 0.5 } else { 1. };
+                Vec2::new(x + shift, y * HEX_LINE_STEP + HEX_HEIGHT / 2.) * tile_size
+            }
+        }
+    }
+
+    /// Size of a board of `width` by `height` tiles
+    pub fn board_size(self, (width, height): (u16, u16), tile_size: f32) -> Vec2 {
+        let (width, height) = (width as f32, height as f32);
+        match self {
+            Topology::Square | Topology::Torus => Vec2::new(width, height) * tile_size,
+            Topology::Hex => {
+                let shift = if height > 1. { 0.5 } else { 0. };
+                let height = (height - 1.).max(0.) * HEX_LINE_STEP + HEX_HEIGHT;
+                Vec2::new(width + shift, height) * tile_size
+            }
+        }
+    }
+
+    /// Size of a tile sprite of `size` width
+    pub fn sprite_size(self, size: f32) -> Vec2 {
+        match self {
+            Topology::Square | Topology::Torus => Vec2::splat(size),
+            Topology::Hex => Vec2::new(size, size * HEX_HEIGHT),
+        }
+    }
+
+    /// Texture of a tile sprite of `material`, hex tiles without a texture being masked
+    pub fn sprite_texture(self, material: &SpriteMaterial) -> Handle<Image> {
+        if self == Topology::Hex && material.texture == DEFAULT_IMAGE_HANDLE.typed() {
+            HEX_TILE_IMAGE_HANDLE.typed()
+        } else {
+            material.texture.clone()
+        }
+    }
+
+    /// Tile of a board of `dimensions` at `position` from the board anchor, if any
+    pub fn tile_at(
+        self,
+        position: Vec2,
+        tile_size: f32,
+        (width, height): (u16, u16),
+    ) -> Option<Coordinates> {
+        if position.x < 0. || position.y < 0. {
+            return None;
+        }
+        let coordinates = match self {
+            Topology::Square | Topology::Torus => Coordinates {
+                x: (position.x / tile_size) as u16,
+                y: (position.y / tile_size) as u16,
+            },
+            // Hexagons are the tiles closest to their center, so we look for the closest center
+            // around the line and column of the position
+            Topology::Hex => {
+                let line = (position.y / (HEX_LINE_STEP * tile_size)) as i32;
+                let column = (position.x / tile_size) as i32;
+                let mut closest = None;
+                for y in (line - 1).max(0)..=line + 1 {
+                    for x in (column - 1).max(0)..=column + 1 {
+                        let coordinates = Coordinates {
+                            x: x as u16,
+                            y: y as u16,
+                        };
+                        let distance = self
+                            .tile_center(coordinates, tile_size)
+                            .distance_squared(position);
+                        if closest.is_none_or(|(_, d)| distance < d) {
+                            closest = Some((coordinates, distance));
+                        }
+                    }
+                }
+                closest?.0
+            }
+        };
+        (coordinates.x < width && coordinates.y < height).then_some(coordinates)
+    }
+}
+
+/// Generates the white hexagon mask of `HEX_TILE_IMAGE_HANDLE`, transparent around the hexagon
+pub(crate) fn hex_tile_image() -> Image {
+    const WIDTH: usize = 64;
+    let height = (WIDTH as f32 * HEX_HEIGHT).round() as usize;
+    let (half_width, half_height) = (WIDTH as f32 / 2., height as f32 / 2.);
+    let mut data = Vec::with_capacity(WIDTH * height * 4);
+    for y in 0..height {
+        for x in 0..WIDTH {
+            let dx = (x as f32 + 0.5 - half_width).abs();
+            let dy = (y as f32 + 0.5 - half_height).abs();
+            // The slanted edges go from the top vertex to a quarter of the height on the sides
+            let inside = dx <= half_width && dy <= half_height * (1. - dx / (2. * half_width));
+            let alpha = if inside { 255 } else { 0 };
+            data.extend_from_slice(&[255, 255, 255, alpha]);
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: WIDTH as u32,
+            height: height as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
@@ -0,0 +1,473 @@
+// solver.rs
+use crate::components::Coordinates;
+use crate::game::{Game, Outcome};
+use crate::resources::{Tile, TileMap};
+use std::cell::Cell;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Frontier groups with more unknown tiles than this are not enumerated
+pub const MAX_ENUMERATED_TILES: usize = 40;
+/// Search nodes a `solves_from` check may visit, boards needing more count as needing a guess
+pub const SOLVE_NODE_BUDGET: u64 = 200_000;
+
+/// Tiles proven safe or proven to be bombs
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Deductions {
+    /// Covered tiles which can be safely uncovered, sorted
+    pub safe: Vec<Coordinates>,
+    /// Covered unflagged tiles which hold a bomb, sorted
+    pub bombs: Vec<Coordinates>,
+}
+
+impl Deductions {
+    pub fn is_empty(&self) -> bool {
+        self.safe.is_empty() && self.bombs.is_empty()
+    }
+}
+
+/// Can `tile_map` be cleared from `start` without guessing, playing every solver deduction.
+///
+/// Boards whose enumerations take more than `SOLVE_NODE_BUDGET` search nodes in all are
+/// considered to need guessing
+pub fn solves_from(tile_map: &TileMap, start: Coordinates) -> bool {
+    let mut game = Game::from_tile_map(tile_map.clone(), 0);
+    game.reveal(start);
+    let mut nodes = SOLVE_NODE_BUDGET;
+    loop {
+        if let Some(outcome) = game.outcome() {
+            return outcome == Outcome::Won;
+        }
+        let solver = Solver::from_game(&game).with_node_budget(nodes);
+        let deductions = solver.solve();
+        nodes = solver.nodes_left();
+        if nodes == 0 || deductions.safe.is_empty() {
+            return false;
+        }
+        for coords in deductions.bombs {
+            game.toggle_flag(coords);
+        }
+        for coords in deductions.safe {
+            game.reveal(coords);
+        }
+    }
+}
+
+/// Number of bombs among unknown tiles, as told by uncovered bomb neighbors
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub(crate) struct Constraint {
+    /// Unknown tiles, sorted
+    pub tiles: Vec<Coordinates>,
+    /// Bombs among `tiles`
+    pub bombs: usize,
+}
+
+impl Constraint {
+    /// Are all our tiles in `other`
+    fn is_subset_of(&self, other: &Self) -> bool {
+        self.tiles.len() <= other.tiles.len()
+            && self
+                .tiles
+                .iter()
+                .all(|tile| other.tiles.binary_search(tile).is_ok())
+    }
+}
+
+/// Solutions of a group of linked constraints
+#[derive(Debug, Clone)]
+pub(crate) struct Enumeration {
+    /// Unknown tiles of the group
+    pub tiles: Vec<Coordinates>,
+    /// Number of solutions, indexed by their bomb count
+    pub solutions: Vec<u64>,
+    /// Number of solutions with a bomb on each tile, indexed by solution bomb count then tile
+    pub tile_bombs: Vec<Vec<u64>>,
+}
+
+/// Logic solver working on the visible state of a board: uncovered tiles and flags.
+///
+/// Flags are trusted to be bombs, and covered tiles of the `TileMap` are never looked at
+#[derive(Debug, Clone)]
+pub struct Solver<'a> {
+    tile_map: &'a TileMap,
+    bomb_count: usize,
+    covered: HashSet<Coordinates>,
+    flagged: HashSet<Coordinates>,
+    /// Uncovered tiles next to covered unflagged ones, the only ones giving constraints
+    frontier: Vec<Coordinates>,
+    /// Search nodes left for the enumerations
+    nodes_left: Cell<u64>,
+}
+
+impl<'a> Solver<'a> {
+    pub fn new(
+        tile_map: &'a TileMap,
+        covered: impl IntoIterator<Item = Coordinates>,
+        flagged: impl IntoIterator<Item = Coordinates>,
+    ) -> Self {
+        let covered: HashSet<_> = covered.into_iter().collect();
+        let flagged: HashSet<_> = flagged
+            .into_iter()
+            .filter(|c| covered.contains(c))
+            .collect();
+        let frontier: HashSet<_> = covered
+            .iter()
+            .filter(|c| !flagged.contains(c))
+            .flat_map(|c| tile_map.safe_square_at(*c))
+            .filter(|c| !covered.contains(c))
+            .collect();
+        Self {
+            tile_map,
+            bomb_count: tile_map.bomb_count() as usize,
+            covered,
+            flagged,
+            frontier: frontier.into_iter().collect(),
+            nodes_left: Cell::new(u64::MAX),
+        }
+    }
+
+    /// Limits the enumerations to `nodes` search nodes, the groups left unfinished deducing nothing
+    pub fn with_node_budget(self, nodes: u64) -> Self {
+        self.nodes_left.set(nodes);
+        self
+    }
+
+    /// Search nodes left, 0 once the enumerations ran out of them
+    pub fn nodes_left(&self) -> u64 {
+        self.nodes_left.get()
+    }
+
+    /// Solver for the current state of `game`
+    pub fn from_game(game: &'a Game) -> Self {
+        let mut solver = Self::new(game.tile_map(), game.covered_tiles(), game.flagged_tiles());
+        solver.bomb_count = game.bomb_count() as usize;
+        solver
+    }
+
+    /// Finds every covered tile which is provably safe or provably a bomb
+    pub fn solve(&self) -> Deductions {
+        let mut deductions = Deductions::default();
+        for (coords, bomb) in self.deduce() {
+            if bomb {
+                deductions.bombs.push(coords);
+            } else {
+                deductions.safe.push(coords);
+            }
+        }
+        deductions.safe.sort();
+        deductions.bombs.sort();
+        deductions
+    }
+
+    /// Deduced tiles, `true` for bombs
+    pub(crate) fn deduce(&self) -> HashMap<Coordinates, bool> {
+        let mut known = HashMap::new();
+        loop {
+            let mut constraints = self.constraints(&known);
+            if self.global_deductions(&mut known)
+                || Self::reduce(&mut constraints, &mut known)
+                || self.enumerate_deductions(&constraints, &mut known)
+            {
+                continue;
+            }
+            break;
+        }
+        known
+    }
+
+    /// Covered tiles which are neither flagged nor deduced
+    pub(crate) fn unknown_tiles<'k>(
+        &'k self,
+        known: &'k HashMap<Coordinates, bool>,
+    ) -> impl Iterator<Item = Coordinates> + 'k {
+        self.covered
+            .iter()
+            .copied()
+            .filter(|c| !self.flagged.contains(c) && !known.contains_key(c))
+    }
+
+    /// Bombs left among the unknown tiles, `None` if there are more flags than bombs
+    pub(crate) fn remaining_bombs(&self, known: &HashMap<Coordinates, bool>) -> Option<usize> {
+        let found = self.flagged.len() + known.values().filter(|bomb| **bomb).count();
+        self.bomb_count.checked_sub(found)
+    }
+
+    /// Builds the constraints of every uncovered tile with unknown neighbors
+    pub(crate) fn constraints(&self, known: &HashMap<Coordinates, bool>) -> BTreeSet<Constraint> {
+        let mut constraints = BTreeSet::new();
+        for coords in self.frontier.iter().copied() {
+            let count = match self.tile_map.tile_at(coords) {
+                Some(Tile::BombNeighbor(count)) => *count as usize,
+                Some(Tile::Empty) => 0,
+                _ => continue,
+            };
+            if let Some(constraint) = self.constraint_at(coords, count, known) {
+                constraints.insert(constraint);
+            }
+        }
+        constraints
+    }
+
+    fn constraint_at(
+        &self,
+        coords: Coordinates,
+        count: usize,
+        known: &HashMap<Coordinates, bool>,
+    ) -> Option<Constraint> {
+        let mut bombs = count;
+        let mut tiles = Vec::new();
+        for neighbor in self.tile_map.safe_square_at(coords) {
+            if !self.covered.contains(&neighbor) {
+                continue;
+            }
+            if self.flagged.contains(&neighbor) || known.get(&neighbor) == Some(&true) {
+                // Wrong flags can make the count negative, we ignore the tile then
+                bombs = bombs.checked_sub(1)?;
+            } else if !known.contains_key(&neighbor) {
+                tiles.push(neighbor);
+            }
+        }
+        if tiles.is_empty() || bombs > tiles.len() {
+            return None;
+        }
+        tiles.sort();
+        Some(Constraint { tiles, bombs })
+    }
+
+    /// Uses the total bomb count when no bomb or only bombs are left
+    fn global_deductions(&self, known: &mut HashMap<Coordinates, bool>) -> bool {
+        let remaining = match self.remaining_bombs(known) {
+            None => return false,
+            Some(r) => r,
+        };
+        // Deduced tiles are covered and unflagged
+        let unknown = self.covered.len() - self.flagged.len() - known.len();
+        if unknown == 0 || (remaining != 0 && remaining != unknown) {
+            return false;
+        }
+        let unknown: Vec<_> = self.unknown_tiles(known).collect();
+        for coords in unknown {
+            known.insert(coords, remaining != 0);
+        }
+        true
+    }
+
+    /// Applies single tile rules and subset reduction, returns `true` if tiles were deduced
+    fn reduce(
+        constraints: &mut BTreeSet<Constraint>,
+        known: &mut HashMap<Coordinates, bool>,
+    ) -> bool {
+        loop {
+            // Single tile rules
+            let mut found = false;
+            for constraint in constraints.iter() {
+                let bomb = match constraint.bombs {
+                    0 => false,
+                    n if n == constraint.tiles.len() => true,
+                    _ => continue,
+                };
+                for tile in constraint.tiles.iter() {
+                    found |= known.insert(*tile, bomb).is_none();
+                }
+            }
+            if found {
+                return true;
+            }
+            // Subset reduction: if `a` is in `b`, the tiles of `b` missing from `a`
+            // hold the bombs of `b` missing from `a`
+            let list: Vec<_> = constraints.iter().collect();
+            let mut by_tile: HashMap<Coordinates, Vec<usize>> = HashMap::new();
+            for (i, constraint) in list.iter().enumerate() {
+                for tile in constraint.tiles.iter() {
+                    by_tile.entry(*tile).or_default().push(i);
+                }
+            }
+            let mut reduced = Vec::new();
+            for a in list.iter() {
+                for b in by_tile[&a.tiles[0]].iter().map(|i| list[*i]) {
+                    if a.tiles.len() < b.tiles.len() && b.bombs >= a.bombs && a.is_subset_of(b) {
+                        let tiles = b
+                            .tiles
+                            .iter()
+                            .filter(|t| a.tiles.binary_search(t).is_err())
+                            .copied()
+                            .collect();
+                        reduced.push(Constraint {
+                            tiles,
+                            bombs: b.bombs - a.bombs,
+                        });
+                    }
+                }
+            }
+            let mut added = false;
+            for constraint in reduced {
+                added |= constraints.insert(constraint);
+            }
+            if !added {
+                return false;
+            }
+        }
+    }
+
+    /// Enumerates every solution of each frontier group, deducing the tiles which are
+    /// safe or bombs in all of them
+    fn enumerate_deductions(
+        &self,
+        constraints: &BTreeSet<Constraint>,
+        known: &mut HashMap<Coordinates, bool>,
+    ) -> bool {
+        let max_bombs = match self.remaining_bombs(known) {
+            None => return false,
+            Some(r) => r,
+        };
+        let mut found = false;
+        for enumeration in self.enumerate(constraints, max_bombs) {
+            let total: u64 = enumeration.solutions.iter().sum();
+            if total == 0 {
+                continue;
+            }
+            for (i, tile) in enumeration.tiles.iter().enumerate() {
+                let bombs: u64 = enumeration.tile_bombs.iter().map(|t| t[i]).sum();
+                if bombs == 0 || bombs == total {
+                    known.insert(*tile, bombs != 0);
+                    found = true;
+                }
+            }
+        }
+        found
+    }
+
+    /// Splits `constraints` in groups sharing tiles and enumerates the solutions of
+    /// each group with at most `max_bombs` bombs. Groups too large, or left unfinished when
+    /// the node budget runs out, are skipped
+    pub(crate) fn enumerate(
+        &self,
+        constraints: &BTreeSet<Constraint>,
+        max_bombs: usize,
+    ) -> Vec<Enumeration> {
+        let mut groups: Vec<(HashSet<Coordinates>, Vec<&Constraint>)> = Vec::new();
+        for constraint in constraints.iter() {
+            let (mut tiles, mut members) = (HashSet::new(), vec![constraint]);
+            tiles.extend(constraint.tiles.iter().copied());
+            // Merges every group sharing a tile with the constraint
+            let mut i = 0;
+            while i < groups.len() {
+                if constraint.tiles.iter().any(|t| groups[i].0.contains(t)) {
+                    let (group_tiles, group_members) = groups.swap_remove(i);
+                    tiles.extend(group_tiles);
+                    members.extend(group_members);
+                } else {
+                    i += 1;
+                }
+            }
+            groups.push((tiles, members));
+        }
+        groups
+            .into_iter()
+            .filter(|(tiles, _)| tiles.len() <= MAX_ENUMERATED_TILES)
+            .filter_map(|(tiles, members)| self.enumerate_group(tiles, &members, max_bombs))
+            .collect()
+    }
+
+    /// Enumeration of a group, `None` if the node budget runs out
+    fn enumerate_group(
+        &self,
+        tiles: HashSet<Coordinates>,
+        constraints: &[&Constraint],
+        max_bombs: usize,
+    ) -> Option<Enumeration> {
+        // Constraint order keeps linked tiles close, which prunes the search early
+        let mut ordered: Vec<Coordinates> = Vec::with_capacity(tiles.len());
+        for constraint in constraints.iter() {
+            for tile in constraint.tiles.iter() {
+                if !ordered.contains(tile) {
+                    ordered.push(*tile);
+                }
+            }
+        }
+        let index: HashMap<Coordinates, usize> =
+            ordered.iter().enumerate().map(|(i, t)| (*t, i)).collect();
+        let search = Search {
+            constraints: constraints
+                .iter()
+                .map(|c| (c.tiles.iter().map(|t| index[t]).collect(), c.bombs))
+                .collect(),
+            tile_constraints: (0..ordered.len())
+                .map(|i| {
+                    constraints
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, c)| c.tiles.binary_search(&ordered[i]).is_ok())
+                        .map(|(j, _)| j)
+                        .collect()
+                })
+                .collect(),
+            max_bombs: max_bombs.min(ordered.len()),
+            nodes_left: &self.nodes_left,
+        };
+        let mut enumeration = Enumeration {
+            solutions: vec![0; search.max_bombs + 1],
+            tile_bombs: vec![vec![0; ordered.len()]; search.max_bombs + 1],
+            tiles: ordered,
+        };
+        let mut assignment = Vec::with_capacity(enumeration.tiles.len());
+        search.run(&mut assignment, &mut enumeration);
+        (self.nodes_left.get() > 0).then_some(enumeration)
+    }
+}
+
+/// Backtracking search over the tiles of a constraint group
+struct Search<'n> {
+    /// Tile indices and bomb count of each constraint
+    constraints: Vec<(Vec<usize>, usize)>,
+    /// Constraint indices of each tile
+    tile_constraints: Vec<Vec<usize>>,
+    max_bombs: usize,
+    /// Nodes left to visit, the search stops at 0
+    nodes_left: &'n Cell<u64>,
+}
+
+impl Search<'_> {
+    fn run(&self, assignment: &mut Vec<bool>, enumeration: &mut Enumeration) {
+        match self.nodes_left.get() {
+            0 => return,
+            n => self.nodes_left.set(n - 1),
+        }
+        let bombs = assignment.iter().filter(|b| **b).count();
+        if assignment.len() == self.tile_constraints.len() {
+            enumeration.solutions[bombs] += 1;
+            for (i, bomb) in assignment.iter().enumerate() {
+                if *bomb {
+                    enumeration.tile_bombs[bombs][i] += 1;
+                }
+            }
+            return;
+        }
+        for bomb in [false, true] {
+            if bomb && bombs == self.max_bombs {
+                continue;
+            }
+            assignment.push(bomb);
+            if self.is_consistent(assignment) {
+                self.run(assignment, enumeration);
+            }
+            assignment.pop();
+        }
+    }
+
+    /// Checks the constraints of the last assigned tile
+    fn is_consistent(&self, assignment: &[bool]) -> bool {
+        let tile = assignment.len() - 1;
+        self.tile_constraints[tile].iter().all(|c| {
+            let (tiles, bombs) = &self.constraints[*c];
+            let (mut placed, mut open) = (0, 0);
+            for t in tiles.iter() {
+                match assignment.get(*t) {
+                    Some(true) => placed += 1,
+                    Some(false) => (),
+                    None => open += 1,
+                }
+            }
+            placed <= *bombs && placed + open >= *bombs
+        })
+    }
+}
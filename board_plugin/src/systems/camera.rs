@@ -0,0 +1,103 @@
+// camera.rs
+use crate::resources::{BoardAction, CameraControls, InputBindings};
+use crate::{Board, BoardCamera};
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+
+/// Mouse wheel pixels counting as a wheel notch
+const PIXELS_PER_LINE: f32 = 20.;
+
+/// Pans and zooms the `BoardCamera` with the `InputBindings` and the mouse wheel.
+///
+/// Zooming keeps the world point under the mouse in place, and a new board recenters the camera
+/// on it at scale 1. Laying the same board out again, on window resize, keeps the camera as is
+#[allow(clippy::too_many_arguments)]
+pub fn camera_controls(
+    windows: Res<Windows>,
+    board: Res<Board>,
+    bindings: Res<InputBindings>,
+    controls: Res<CameraControls>,
+    time: Res<Time>,
+    keys: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepads: Res<Gamepads>,
+    mut wheel_evr: EventReader<MouseWheel>,
+    mut cameras: Query<
+        (&Camera, &GlobalTransform, &mut Transform, &mut OrthographicProjection),
+        With<BoardCamera>,
+    >,
+    // Entity of the board shown by the camera, and the last mouse position for the drag
+    mut shown_board: Local<Option<Entity>>,
+    mut last_mouse: Local<Option<Vec2>>,
+) {
+    let (camera, global_transform, mut transform, mut projection) = match cameras.get_single_mut() {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+    if *shown_board != Some(board.entity) {
+        *shown_board = Some(board.entity);
+        let center = board.bounds.position + board.bounds.size / 2.;
+        transform.translation.x = center.x;
+        transform.translation.y = center.y;
+        projection.scale = 1.;
+    }
+    let mouse = windows.get_primary().and_then(|w| w.cursor_position());
+    let held = |action: BoardAction| {
+        bindings.keys(action).any(|k| keys.pressed(k))
+            || bindings.mouse_buttons(action).any(|b| mouse_buttons.pressed(b))
+            || gamepads.iter().any(|gamepad| {
+                bindings
+                    .gamepad_buttons(action)
+                    .any(|b| gamepad_buttons.pressed(GamepadButton::new(gamepad, b)))
+            })
+    };
+    let just_pressed = |action: BoardAction| {
+        bindings.keys(action).any(|k| keys.just_pressed(k))
+            || bindings.mouse_buttons(action).any(|b| mouse_buttons.just_pressed(b))
+            || gamepads.iter().any(|gamepad| {
+                bindings
+                    .gamepad_buttons(action)
+                    .any(|b| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, b)))
+            })
+    };
+
+    // Pan
+    let scale = projection.scale;
+    let direction = Vec2::new(
+        held(BoardAction::PanRight) as i32 as f32 - held(BoardAction::PanLeft) as i32 as f32,
+        held(BoardAction::PanUp) as i32 as f32 - held(BoardAction::PanDown) as i32 as f32,
+    );
+    let mut pan = direction * controls.pan_speed * scale * time.delta_seconds();
+    if held(BoardAction::Pan) {
+        if let (Some(mouse), Some(last)) = (mouse, *last_mouse) {
+            // The board follows the mouse
+            pan -= (mouse - last) * scale;
+        }
+    }
+    *last_mouse = mouse;
+    transform.translation += pan.extend(0.);
+
+    // Zoom
+    let notches = wheel_evr
+        .iter()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+        })
+        .sum::<f32>()
+        + just_pressed(BoardAction::ZoomIn) as i32 as f32
+        - just_pressed(BoardAction::ZoomOut) as i32 as f32;
+    if notches == 0. {
+        return;
+    }
+    let new_scale = (scale / controls.zoom_step.powf(notches))
+        .clamp(controls.min_scale, controls.max_scale);
+    let anchor = mouse
+        .and_then(|m| camera.viewport_to_world(global_transform, m))
+        .map(|ray| ray.origin.truncate() - global_transform.translation().truncate());
+    if let Some(offset) = anchor {
+        transform.translation += (offset * (1. - new_scale / scale)).extend(0.);
+    }
+    projection.scale = new_scale;
+}
@@ -0,0 +1,224 @@
+// chunks.rs
+use crate::resources::{BoardAssets, BoardOptions};
+use crate::{Board, Coordinates, Tile, TileChunk};
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::texture::ImageSampler;
+use bevy::sprite::Mesh2dHandle;
+use bevy::utils::HashSet;
+
+/// Atlas cell size, in pixels
+const CELL_PIXELS: usize = 16;
+/// Atlas cells: covered, flagged, empty, the 8 bomb counts and bomb
+const CELLS: usize = 12;
+const COVERED: u8 = 0;
+const FLAGGED: u8 = 1;
+const EMPTY: u8 = 2;
+const BOMB: u8 = 11;
+
+/// 3x5 glyphs of the bomb counts, from the top row, drawn twice as big
+const DIGITS: [[&str; 5]; 8] = [
+    [".#.", "##.", ".#.", ".#.", "###"],
+    ["##.", "..#", ".#.", "#..", "###"],
+    ["##.", "..#", ".#.", "..#", "##."],
+    ["#.#", "#.#", "###", "..#", "..#"],
+    ["###", "#..", "##.", "..#", "##."],
+    [".##", "#..", "###", "#.#", "###"],
+    ["###", "..#", ".#.", ".#.", ".#."],
+    ["###", "#.#", "###", "#.#", "###"],
+];
+const FLAG_GLYPH: [&str; 5] = ["###", "##.", "#..", "#..", "#.."];
+const BOMB_GLYPH: [&str; 5] = [".#.", "###", "###", "###", ".#."];
+
+/// Draws the chunks of `RenderMode::Chunks` boards, updating only the meshes of the chunks
+/// holding `Board::changed_tiles`, or all of them when the tile size changes.
+///
+/// Nothing is drawn without the mesh, image and color material assets, like in headless apps
+#[allow(clippy::too_many_arguments)]
+pub fn draw_chunks(
+    mut commands: Commands,
+    mut board: ResMut<Board>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    images: Option<ResMut<Assets<Image>>>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+    materials: Option<ResMut<Assets<ColorMaterial>>>,
+    // Material of the tile atlas, generated from the board assets
+    mut atlas: Local<Option<Handle<ColorMaterial>>>,
+    mut chunks: Query<(Entity, &mut TileChunk, Option<&Mesh2dHandle>)>,
+) {
+    // Taken every frame, drawn or not, so the changes don't pile up
+    let changed_tiles = if board.changed_tiles.is_empty() {
+        Vec::new()
+    } else {
+        std::mem::take(&mut board.bypass_change_detection().changed_tiles)
+    };
+    let (mut images, mut meshes, mut materials) = match (images, meshes, materials) {
+        (Some(i), Some(m), Some(c)) => (i, m, c),
+        _ => return,
+    };
+    let undrawn = chunks.iter().any(|(_, chunk, _)| chunk.drawn.is_none());
+    if !undrawn && !board.is_changed() && !board_assets.is_changed() {
+        return;
+    }
+    // Chunks are identified by their origin, a multiple of the size of the full chunks
+    let (width, height) = chunks
+        .iter()
+        .fold((1, 1), |(w, h), (_, chunk, _)| (chunk.size.0.max(w), chunk.size.1.max(h)));
+    let dirty: HashSet<Coordinates> = changed_tiles
+        .iter()
+        .map(|c| Coordinates {
+            x: c.x - c.x % width,
+            y: c.y - c.y % height,
+        })
+        .collect();
+    if atlas.is_none() || board_assets.is_changed() {
+        let texture = images.add(tile_atlas(&board_assets));
+        match atlas.as_ref().and_then(|handle| materials.get_mut(handle)) {
+            Some(material) => material.texture = Some(texture),
+            None => *atlas = Some(materials.add(ColorMaterial::from(texture))),
+        }
+    }
+    let material = match atlas.as_ref() {
+        None => return,
+        Some(m) => m.clone(),
+    };
+    let padding = board_options.map_or(0., |o| o.tile_padding);
+    for (entity, mut chunk, mesh) in chunks.iter_mut() {
+        let up_to_date = chunk
+            .drawn
+            .as_ref()
+            .is_some_and(|(_, size)| *size == board.tile_size);
+        if up_to_date && !dirty.contains(&chunk.origin) {
+            continue;
+        }
+        let cells = chunk_cells(&board, &chunk);
+        if up_to_date && chunk.drawn.as_ref().is_some_and(|(drawn, _)| *drawn == cells) {
+            continue;
+        }
+        let new_mesh = chunk_mesh(chunk.size, &cells, board.tile_size, padding);
+        match mesh.and_then(|handle| meshes.get_mut(&handle.0)) {
+            Some(mesh) => *mesh = new_mesh,
+            None => {
+                commands
+                    .entity(entity)
+                    .insert((Mesh2dHandle(meshes.add(new_mesh)), material.clone()));
+            }
+        }
+        chunk.drawn = Some((cells, board.tile_size));
+    }
+}
+
+/// Atlas cell of each tile of `chunk`, line by line from the bottom
+fn chunk_cells(board: &Board, chunk: &TileChunk) -> Vec<u8> {
+    let game = &board.game;
+    let mut cells = Vec::with_capacity(chunk.size.0 as usize * chunk.size.1 as usize);
+    for y in chunk.origin.y..chunk.origin.y + chunk.size.1 {
+        for x in chunk.origin.x..chunk.origin.x + chunk.size.0 {
+            let coords = Coordinates { x, y };
+            let cell = if game.is_flagged(coords) {
+                FLAGGED
+            } else if game.is_covered(coords) {
+                COVERED
+            } else {
+                match game.tile_map().tile_at(coords) {
+                    Some(Tile::Bomb) => BOMB,
+                    Some(Tile::BombNeighbor(count)) => EMPTY + count,
+                    _ => EMPTY,
+                }
+            };
+            cells.push(cell);
+        }
+    }
+    cells
+}
+
+/// Mesh of a chunk of `size` tiles, each tile quad showing its atlas cell
+fn chunk_mesh((width, _): (u16, u16), cells: &[u8], tile_size: f32, padding: f32) -> Mesh {
+    let mut positions = Vec::with_capacity(cells.len() * 4);
+    let mut uvs = Vec::with_capacity(cells.len() * 4);
+    let mut indices = Vec::with_capacity(cells.len() * 6);
+    let atlas_width = (CELLS * CELL_PIXELS) as f32;
+    // Half a pixel inset keeps the neighbor cells out
+    let inset = 0.5 / CELL_PIXELS as f32;
+    for (i, cell) in cells.iter().enumerate() {
+        let (x, y) = ((i % width as usize) as f32, (i / width as usize) as f32);
+        let (left, bottom) = (x * tile_size + padding / 2., y * tile_size + padding / 2.);
+        let (right, top) = (left + tile_size - padding, bottom + tile_size - padding);
+        let u_left = (*cell as usize * CELL_PIXELS) as f32 / atlas_width + inset / CELLS as f32;
+        let u_right = u_left + 1. / CELLS as f32 - 2. * inset / CELLS as f32;
+        let first = positions.len() as u32;
+        positions.extend([
+            [left, bottom, 0.],
+            [right, bottom, 0.],
+            [right, top, 0.],
+            [left, top, 0.],
+        ]);
+        uvs.extend([
+            [u_left, 1. - inset],
+            [u_right, 1. - inset],
+            [u_right, inset],
+            [u_left, inset],
+        ]);
+        indices.extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+    }
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0., 0., 1.]; positions.len()]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh
+}
+
+/// Generates the tile atlas with the board asset colors, a cell per tile look
+fn tile_atlas(board_assets: &BoardAssets) -> Image {
+    let rgba = |color: Color| color.as_rgba_f32().map(|c| (c.clamp(0., 1.) * 255.) as u8);
+    let row_pixels = CELLS * CELL_PIXELS;
+    let mut data = vec![0; row_pixels * CELL_PIXELS * 4];
+    for cell in 0..CELLS as u8 {
+        let (background, glyph) = match cell {
+            COVERED => (board_assets.covered_tile_material.color, None),
+            FLAGGED => (
+                board_assets.covered_tile_material.color,
+                Some((board_assets.flag_material.color, &FLAG_GLYPH)),
+            ),
+            BOMB => (
+                board_assets.tile_material.color,
+                Some((board_assets.bomb_material.color, &BOMB_GLYPH)),
+            ),
+            EMPTY => (board_assets.tile_material.color, None),
+            count => (
+                board_assets.tile_material.color,
+                Some((
+                    board_assets.bomb_counter_color(count - EMPTY),
+                    &DIGITS[(count - EMPTY - 1) as usize],
+                )),
+            ),
+        };
+        for y in 0..CELL_PIXELS {
+            for x in 0..CELL_PIXELS {
+                // The glyph is centered, each glyph pixel covers 2x2 atlas pixels
+                let (gx, gy) = ((x as isize - 5) / 2, (y as isize - 3) / 2);
+                let lit = glyph.filter(|_| x >= 5 && y >= 3).and_then(|(color, rows)| {
+                    let row = rows.get(gy as usize)?.as_bytes();
+                    (row.get(gx as usize) == Some(&b'#')).then_some(color)
+                });
+                let offset = (y * row_pixels + cell as usize * CELL_PIXELS + x) * 4;
+                data[offset..offset + 4].copy_from_slice(&rgba(lit.unwrap_or(background)));
+            }
+        }
+    }
+    let mut image = Image::new(
+        Extent3d {
+            width: row_pixels as u32,
+            height: CELL_PIXELS as u32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    image.sampler_descriptor = ImageSampler::nearest();
+    image
+}
@@ -1,51 +1,57 @@
-// mark.rs
-use crate::{events::BombExplosionEvent, resources::BoardAssets, Board};
+// fail.rs
+use crate::{events::GameStatusChangedEvent, resources::{BoardAssets, GameStatus}, Board, GameOver};
 use bevy::prelude::*;
 
 pub fn fail(
     mut commands: Commands,
-    mut board: ResMut<Board>,
-    mut bomb_explose_rdr: EventReader<BombExplosionEvent>,
+    board: Res<Board>,
+    mut status_changed_rdr: EventReader<GameStatusChangedEvent>,
     board_assets: Res<BoardAssets>,
 ) {
-    for _ in bomb_explose_rdr.iter() {
-        
-        board.as_mut().set_need_stop_state(true);
+    for _ in status_changed_rdr.iter().filter(|e| e.0 == GameStatus::Lost) {
+        spawn_game_over(&mut commands, &board, &board_assets, "GAME OVER!");
+    }
+}
 
-        let middle_pos = board.get_middle_pos();
-        let board_entry = board.as_ref().entity;
-        let board_size = board.get_size();
-        let text_bundle = Text2dBundle {
-            text: Text {
-                sections: vec![TextSection {
-                    value: "GAME OVER!".to_string(),
-                    style: TextStyle {
-                        color: board_assets.fail_material.color,
-                        font: board_assets.bomb_counter_font.clone(),
-                        font_size: board_assets.game_over_font_size,
-                    },
-                }],
-                alignment: TextAlignment {
-                    vertical: VerticalAlign::Center,
-                    horizontal: HorizontalAlign::Center,
+/// Covers the board with the game over `message`, until the next board
+pub(crate) fn spawn_game_over(
+    commands: &mut Commands,
+    board: &Board,
+    board_assets: &BoardAssets,
+    message: &str,
+) {
+    let middle_pos = board.get_middle_pos();
+    let board_size = board.get_size();
+    let text_bundle = Text2dBundle {
+        text: Text {
+            sections: vec![TextSection {
+                value: message.to_string(),
+                style: TextStyle {
+                    color: board_assets.fail_material.color,
+                    font: board_assets.bomb_counter_font.clone(),
+                    font_size: board_assets.game_over_font_size,
                 },
+            }],
+            alignment: TextAlignment {
+                vertical: VerticalAlign::Center,
+                horizontal: HorizontalAlign::Center,
             },
-            transform: Transform::from_xyz(middle_pos.x, middle_pos.y, 11.),
-            ..Default::default()
-        };
+        },
+        transform: Transform::from_xyz(middle_pos.x, middle_pos.y, 11.),
+        ..Default::default()
+    };
 
-        commands.entity(board_entry).with_children(|parent| {
-            parent
-                .spawn(SpriteBundle {
-                    sprite: Sprite {
-                        color: board_assets.fail_cover_board_material.color,
-                        custom_size: Some(board_size),
-                        ..Default::default()
-                    },
-                    texture: board_assets.board_material.texture.clone(),
+    commands.entity(board.entity).with_children(|parent| {
+        parent
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: board_assets.fail_cover_board_material.color,
+                    custom_size: Some(board_size),
                     ..Default::default()
-                })
-                .insert(Name::new("Cover_board")).insert(text_bundle);
-        });
-    }
+                },
+                texture: board_assets.board_material.texture.clone(),
+                ..Default::default()
+            })
+            .insert(Name::new("Cover_board")).insert(GameOver).insert(text_bundle);
+    });
 }
@@ -0,0 +1,52 @@
+// high_scores.rs
+use crate::events::BoardCompletedEvent;
+use crate::resources::{
+    BoardOptions, GameTimer, HighScore, HighScores, PlayerName, ReplayPlayback, ScoreKey,
+};
+use crate::Board;
+use bevy::log;
+use bevy::prelude::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Records won games in the `HighScores`, if the resource exists. Replayed wins are not recorded
+pub fn record_high_score(
+    board: Res<Board>,
+    board_options: Option<Res<BoardOptions>>,
+    timer: Res<GameTimer>,
+    player: Option<Res<PlayerName>>,
+    high_scores: Option<ResMut<HighScores>>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut board_completed_rdr: EventReader<BoardCompletedEvent>,
+) {
+    let mut high_scores = match high_scores {
+        Some(h) if playback.is_none() => h,
+        _ => {
+            board_completed_rdr.clear();
+            return;
+        }
+    };
+    for _ in board_completed_rdr.iter() {
+        let options = board_options
+            .as_ref()
+            .map_or_else(BoardOptions::default, |o| o.as_ref().clone());
+        // Undone mistakes don't make honest times
+        if options.undo {
+            continue;
+        }
+        let key = ScoreKey::from(&options);
+        let score = HighScore {
+            time: timer.elapsed,
+            date: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            player: player.as_ref().map_or_else(|| "Player".to_string(), |p| p.0.clone()),
+            seed: board.game.seed(),
+        };
+        if let Some(rank) = high_scores.record(key, score) {
+            log::info!("New high score, rank {}", rank + 1);
+            if let Err(e) = high_scores.save() {
+                log::error!("Failed to save high scores: {}", e);
+            }
+        }
+    }
+}
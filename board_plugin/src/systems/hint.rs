@@ -0,0 +1,52 @@
+// hint.rs
+use crate::components::Coordinates;
+use crate::events::HintEvent;
+use crate::probability::bomb_probabilities;
+use crate::solver::{Solver, SOLVE_NODE_BUDGET};
+use crate::systems::input::move_cursor;
+use crate::{Board, Cursor};
+use bevy::log;
+use bevy::prelude::*;
+
+/// Shows the cursor on the hinted tile on `HintEvent`
+pub fn hint_event_handler(
+    board: Res<Board>,
+    mut cursors: Query<(&mut Cursor, &mut Transform, &mut Visibility)>,
+    mut hint_evr: EventReader<HintEvent>,
+) {
+    if hint_evr.iter().count() == 0 {
+        return;
+    }
+    let (mut cursor, mut transform, mut visibility) = match cursors.get_single_mut() {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+    let coords = match hint(&board) {
+        None => return,
+        Some(c) => c,
+    };
+    log::info!("Hint on {}", coords);
+    move_cursor(&board, coords, &mut cursor, &mut transform);
+    visibility.is_visible = true;
+}
+
+/// A tile the solver proves safe, or else the covered tile least likely to be a bomb
+fn hint(board: &Board) -> Option<Coordinates> {
+    let game = &board.game;
+    if game.has_pending_bombs() {
+        // Every tile is safe until the bombs are placed
+        return Some(Coordinates {
+            x: game.tile_map().width() / 2,
+            y: game.tile_map().height() / 2,
+        });
+    }
+    let solver = Solver::from_game(game).with_node_budget(SOLVE_NODE_BUDGET);
+    if let Some(coords) = solver.solve().safe.first() {
+        return Some(*coords);
+    }
+    bomb_probabilities(&solver)?
+        .into_iter()
+        .filter(|(coords, _)| game.is_covered(*coords) && !game.is_flagged(*coords))
+        .min_by(|(a, p), (b, q)| p.total_cmp(q).then(a.cmp(b)))
+        .map(|(coords, _)| coords)
+}
@@ -0,0 +1,81 @@
+// hud.rs
+use crate::events::{BoardCompletedEvent, BombExplosionEvent, TileChordEvent, TileTriggerEvent};
+use crate::resources::{BoardAssets, GameTimer};
+use crate::{Board, Hud};
+use bevy::prelude::*;
+
+pub fn spawn_hud(mut commands: Commands, board_assets: Res<BoardAssets>) {
+    let style = &board_assets.hud;
+    let text_style = TextStyle {
+        font: style.font.clone(),
+        font_size: style.font_size,
+        color: style.color,
+    };
+    commands
+        .spawn(
+            TextBundle::from_sections([
+                TextSection::new("", text_style.clone()),
+                TextSection::new("", text_style),
+            ])
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: style.position,
+                ..Default::default()
+            }),
+        )
+        .insert(Name::new("Hud"))
+        .insert(Hud);
+}
+
+pub fn despawn_hud(mut commands: Commands, huds: Query<Entity, With<Hud>>) {
+    for entity in huds.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Starts the timer on the first move of the player and stops it when the game is over.
+///
+/// The tile uncovered by a safe start is not a move, the time before the first move isn't counted.
+/// New boards reset the timer when they are built
+pub fn update_timer(
+    time: Res<Time>,
+    board: Option<Res<Board>>,
+    mut timer: ResMut<GameTimer>,
+    mut tile_trigger_rdr: EventReader<TileTriggerEvent>,
+    mut tile_chord_rdr: EventReader<TileChordEvent>,
+    mut bomb_explosion_rdr: EventReader<BombExplosionEvent>,
+    mut board_completed_rdr: EventReader<BoardCompletedEvent>,
+) {
+    // Without a board, the only trigger is the safe start of the first one
+    let start_tile = board.as_ref().map(|b| b.start_tile);
+    let moves = tile_trigger_rdr
+        .iter()
+        .filter(|e| start_tile.is_some_and(|start| start != Some(e.0)))
+        .count()
+        + tile_chord_rdr.iter().count();
+    if start_tile.is_some() && moves > 0 {
+        timer.running = true;
+    }
+    if bomb_explosion_rdr.iter().count() + board_completed_rdr.iter().count() > 0 {
+        timer.running = false;
+    }
+    if timer.running {
+        timer.elapsed += time.delta();
+    }
+}
+
+pub fn update_hud(
+    board: Res<Board>,
+    timer: Res<GameTimer>,
+    mut huds: Query<&mut Text, With<Hud>>,
+) {
+    if !board.is_changed() && !timer.is_changed() {
+        return;
+    }
+    // Wrong flags can make the count negative
+    let mines_left = board.game.bomb_count() as i32 - board.game.flag_count() as i32;
+    for mut text in huds.iter_mut() {
+        text.sections[0].value = format!("Mines: {}  ", mines_left);
+        text.sections[1].value = format!("Time: {:03}", timer.elapsed.as_secs());
+    }
+}
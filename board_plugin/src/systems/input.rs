@@ -1,38 +1,89 @@
 // input.rs
-use crate::Board;
+use crate::{Board, Coordinates};
+use crate::resources::{
+    BoardAction, BoardOptions, Binding, ChordInput, GameStatus, InputBindings, ReplayPlayback,
+};
+use crate::{BoardCamera, Cursor};
+use bevy::ecs::system::SystemParam;
 use bevy::input::{mouse::MouseButtonInput, ButtonState};
+use bevy::utils::HashMap;
+use bevy::window::CursorMoved;
 use bevy::log;
 use bevy::prelude::*;
-use crate::events::{TileTriggerEvent, TileMarkEvent};
+use crate::events::{
+    HintEvent, RestartBoardEvent, TileChordEvent, TileTriggerEvent, TileMarkEvent,
+};
 
+/// Left stick tilt moving the cursor
+const STICK_THRESHOLD: f32 = 0.5;
+
+#[allow(clippy::too_many_arguments)]
 pub fn input_handling(
     windows: Res<Windows>,
     board: Res<Board>,
+    status: Res<GameStatus>,
+    board_options: Option<Res<BoardOptions>>,
+    playback: Option<Res<ReplayPlayback>>,
+    bindings: Res<InputBindings>,
+    buttons: Res<Input<MouseButton>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<BoardCamera>>,
     mut button_evr: EventReader<MouseButtonInput>,
     mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
     mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
+    mut restart_ewr: EventWriter<RestartBoardEvent>,
+    mut hint_ewr: EventWriter<HintEvent>,
 ) {
-    let window = windows.get_primary().unwrap();
-    for event in button_evr.iter() {
-        if board.need_stop_listening_pressed {
+    let window = match windows.get_primary() {
+        // Replays play on their own, the presses are dropped so they don't act later
+        Some(w) if playback.is_none() => w,
+        _ => {
+            button_evr.clear();
             return;
         }
+    };
+    let chord_input = board_options.map_or_else(ChordInput::default, |o| o.chord_input);
+    let bound_pressed = |action: BoardAction| bindings.mouse_buttons(action).any(|b| buttons.pressed(b));
+    for event in button_evr.iter() {
         if let ButtonState::Pressed = event.state {
+            let binding = Binding::Mouse(event.button);
+            if bindings.is_bound(BoardAction::Restart, binding) {
+                log::info!("Restarting board");
+                restart_ewr.send(RestartBoardEvent);
+            }
+            if status.is_over() {
+                continue;
+            }
+            if bindings.is_bound(BoardAction::Hint, binding) {
+                hint_ewr.send(HintEvent);
+            }
             let position = window.cursor_position();
             if let Some(pos) = position {
                 log::trace!("Mouse button pressed: {:?} at {}", event.button, pos);
-                let tile_coordinates = board.mouse_position(window, pos);
+                let tile_coordinates = board.mouse_position(window, cameras.iter().next(), pos);
                 if let Some(coordinates) = tile_coordinates {
-                    match event.button {
-                        MouseButton::Left => {
-                            log::info!("Trying to uncover tile on {}", coordinates);
-                            tile_trigger_ewr.send(TileTriggerEvent(coordinates));
+                    let chord = match chord_input {
+                        ChordInput::MiddleClick => bindings.is_bound(BoardAction::Chord, binding),
+                        ChordInput::BothButtons => {
+                            bound_pressed(BoardAction::Reveal) && bound_pressed(BoardAction::Flag)
                         }
-                        MouseButton::Right => {
-                            log::info!("Trying to mark tile on {}", coordinates);
-                            tile_mark_ewr.send(TileMarkEvent(coordinates));
+                        ChordInput::LeftClickOnNumber => {
+                            bindings.is_bound(BoardAction::Reveal, binding)
+                                && !board.game.is_covered(coordinates)
                         }
-                        _ => (),
+                    };
+                    if chord {
+                        log::info!("Trying to chord tile on {}", coordinates);
+                        tile_chord_ewr.send(TileChordEvent(coordinates));
+                        continue;
+                    }
+                    if bindings.is_bound(BoardAction::Reveal, binding) {
+                        log::info!("Trying to uncover tile on {}", coordinates);
+                        tile_trigger_ewr.send(TileTriggerEvent(coordinates));
+                    }
+                    if bindings.is_bound(BoardAction::Flag, binding) {
+                        log::info!("Trying to mark tile on {}", coordinates);
+                        tile_mark_ewr.send(TileMarkEvent(coordinates));
                     }
                 }
             }
@@ -40,3 +91,177 @@ pub fn input_handling(
     }
 }
 
+/// Cursor moves and board actions read from a device in a frame
+#[derive(Debug, Default, Copy, Clone)]
+struct CursorInput {
+    dx: i32,
+    dy: i32,
+    reveal: bool,
+    flag: bool,
+    chord: bool,
+    restart: bool,
+    hint: bool,
+}
+
+/// Board and cursor access shared by the cursor input systems
+#[derive(SystemParam)]
+pub struct CursorContext<'w, 's> {
+    board: Res<'w, Board>,
+    status: Res<'w, GameStatus>,
+    board_options: Option<Res<'w, BoardOptions>>,
+    playback: Option<Res<'w, ReplayPlayback>>,
+    cursors: Query<'w, 's, (&'static mut Cursor, &'static mut Transform, &'static mut Visibility)>,
+    tile_trigger_ewr: EventWriter<'w, 's, TileTriggerEvent>,
+    tile_mark_ewr: EventWriter<'w, 's, TileMarkEvent>,
+    tile_chord_ewr: EventWriter<'w, 's, TileChordEvent>,
+    restart_ewr: EventWriter<'w, 's, RestartBoardEvent>,
+    hint_ewr: EventWriter<'w, 's, HintEvent>,
+}
+
+impl<'w, 's> CursorContext<'w, 's> {
+    fn hide(&mut self) {
+        for (_, _, mut visibility) in self.cursors.iter_mut() {
+            visibility.is_visible = false;
+        }
+    }
+
+    /// Moves the cursor and sends the board events of `input`, like the mouse buttons.
+    ///
+    /// The first cursor input of a hidden cursor only shows it
+    fn apply(&mut self, input: CursorInput) {
+        // Replays play on their own
+        if self.playback.is_some() {
+            return;
+        }
+        if input.restart {
+            log::info!("Restarting board");
+            self.restart_ewr.send(RestartBoardEvent);
+        }
+        if input.hint && !self.status.is_over() {
+            self.hint_ewr.send(HintEvent);
+        }
+        let (mut cursor, mut transform, mut visibility) = match self.cursors.get_single_mut() {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        let acts = input.reveal || input.flag || input.chord;
+        if input.dx == 0 && input.dy == 0 && !acts {
+            return;
+        }
+        if !visibility.is_visible {
+            visibility.is_visible = true;
+            return;
+        }
+        let tile_map = self.board.game.tile_map();
+        let coords = Coordinates {
+            x: (cursor.coordinates.x as i32 + input.dx).clamp(0, tile_map.width() as i32 - 1) as u16,
+            y: (cursor.coordinates.y as i32 + input.dy).clamp(0, tile_map.height() as i32 - 1) as u16,
+        };
+        move_cursor(&self.board, coords, &mut cursor, &mut transform);
+        if self.status.is_over() {
+            return;
+        }
+        let chord_input = self
+            .board_options
+            .as_ref()
+            .map_or_else(ChordInput::default, |o| o.chord_input);
+        let uncovered_number =
+            chord_input == ChordInput::LeftClickOnNumber && !self.board.game.is_covered(coords);
+        if input.chord || (input.reveal && uncovered_number) {
+            log::info!("Trying to chord tile on {}", coords);
+            self.tile_chord_ewr.send(TileChordEvent(coords));
+        } else if input.reveal {
+            log::info!("Trying to uncover tile on {}", coords);
+            self.tile_trigger_ewr.send(TileTriggerEvent(coords));
+        }
+        if input.flag {
+            log::info!("Trying to mark tile on {}", coords);
+            self.tile_mark_ewr.send(TileMarkEvent(coords));
+        }
+    }
+}
+
+/// Puts the cursor on the `coords` tile
+pub(crate) fn move_cursor(
+    board: &Board,
+    coords: Coordinates,
+    cursor: &mut Cursor,
+    transform: &mut Transform,
+) {
+    if coords != cursor.coordinates {
+        cursor.coordinates = coords;
+        let position = board.game.tile_map().topology().tile_center(coords, board.tile_size);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
+
+/// Moves the cursor and acts on its tile with the keys of the `InputBindings`.
+///
+/// The cursor shows up when a cursor key is pressed and hides when the mouse moves
+pub fn keyboard_cursor(
+    mut context: CursorContext,
+    keys: Res<Input<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mut cursor_moved_evr: EventReader<CursorMoved>,
+) {
+    if cursor_moved_evr.iter().count() > 0 {
+        context.hide();
+    }
+    let pressed = |action: BoardAction| bindings.keys(action).any(|k| keys.just_pressed(k));
+    context.apply(CursorInput {
+        dx: pressed(BoardAction::CursorRight) as i32 - pressed(BoardAction::CursorLeft) as i32,
+        dy: pressed(BoardAction::CursorUp) as i32 - pressed(BoardAction::CursorDown) as i32,
+        reveal: pressed(BoardAction::Reveal),
+        flag: pressed(BoardAction::Flag),
+        chord: pressed(BoardAction::Chord),
+        restart: pressed(BoardAction::Restart),
+        hint: pressed(BoardAction::Hint),
+    });
+}
+
+/// Moves the cursor with the left stick and acts on its tile with the gamepad buttons of
+/// the `InputBindings`, on every connected gamepad
+pub fn gamepad_cursor(
+    mut context: CursorContext,
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    bindings: Res<InputBindings>,
+    // Stick direction of each gamepad, the cursor moves once per push
+    mut sticks: Local<HashMap<Gamepad, IVec2>>,
+) {
+    for gamepad in gamepads.iter() {
+        let pressed = |action: BoardAction| {
+            bindings
+                .gamepad_buttons(action)
+                .any(|b| buttons.just_pressed(GamepadButton::new(gamepad, b)))
+        };
+        let axis = |axis_type: GamepadAxisType| {
+            let value = axes
+                .get(GamepadAxis::new(gamepad, axis_type))
+                .unwrap_or_default();
+            if value >= STICK_THRESHOLD {
+                1
+            } else if value <= -STICK_THRESHOLD {
+                -1
+            } else {
+                0
+            }
+        };
+        let stick = IVec2::new(axis(GamepadAxisType::LeftStickX), axis(GamepadAxisType::LeftStickY));
+        let previous = sticks.insert(gamepad, stick).unwrap_or_default();
+        let pushed = |value: i32, previous: i32| if value != previous { value } else { 0 };
+        context.apply(CursorInput {
+            dx: pressed(BoardAction::CursorRight) as i32 - pressed(BoardAction::CursorLeft) as i32
+                + pushed(stick.x, previous.x),
+            dy: pressed(BoardAction::CursorUp) as i32 - pressed(BoardAction::CursorDown) as i32
+                + pushed(stick.y, previous.y),
+            reveal: pressed(BoardAction::Reveal),
+            flag: pressed(BoardAction::Flag),
+            chord: pressed(BoardAction::Chord),
+            restart: pressed(BoardAction::Restart),
+            hint: pressed(BoardAction::Hint),
+        });
+    }
+}
@@ -0,0 +1,98 @@
+// layout.rs
+use crate::resources::{BoardOptions, TileSize};
+use crate::{Board, Coordinates, Cursor, GameOver};
+use bevy::log;
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use bevy::window::{WindowId, WindowResized};
+
+/// Recomputes an adaptive tile size when the primary window is resized, and moves and resizes
+/// the board entities to match it without rebuilding the game
+#[allow(clippy::type_complexity)]
+pub fn relayout_board(
+    windows: Res<Windows>,
+    mut board: ResMut<Board>,
+    board_options: Option<Res<BoardOptions>>,
+    mut resized_evr: EventReader<WindowResized>,
+    children: Query<&Children>,
+    mut nodes: Query<(
+        Option<&mut Transform>,
+        Option<&mut Sprite>,
+        Option<&mut Text>,
+        Option<&Coordinates>,
+        Option<&Cursor>,
+        Option<&GameOver>,
+    )>,
+) {
+    if !resized_evr.iter().any(|e| e.id == WindowId::primary()) {
+        return;
+    }
+    let options = match board_options {
+        None => BoardOptions::default(),
+        Some(o) => o.clone(),
+    };
+    if !matches!(options.tile_size, TileSize::Adaptive { .. }) {
+        return;
+    }
+    let tile_map = board.game.tile_map();
+    let dimensions = (tile_map.width(), tile_map.height());
+    let topology = tile_map.topology();
+    let tile_size = options.tile_size.size(windows.get_primary(), topology, dimensions);
+    let old_size = board.tile_size;
+    if tile_size == old_size {
+        return;
+    }
+    log::info!("tile size: {} -> {}", old_size, tile_size);
+    let board_size = topology.board_size(dimensions, tile_size);
+    let board_position = options.position.anchor(board_size);
+    board.tile_size = tile_size;
+    board.bounds.position = board_position.xy();
+    board.bounds.size = board_size;
+    if let Ok((Some(mut transform), ..)) = nodes.get_mut(board.entity) {
+        transform.translation = board_position;
+    }
+
+    // Tiles and the cursor sit at their coordinates, the background and the game over cover span
+    // the board. The tile, cover, flag and text sizes keep their padding to the tile size, and the
+    // sprites their aspect ratio
+    let ratio = tile_size / old_size;
+    let delta = tile_size - old_size;
+    let middle_pos = board.get_middle_pos();
+    let mut stack: Vec<(Entity, bool)> = children
+        .get(board.entity)
+        .map(|c| c.iter().map(|e| (*e, true)).collect())
+        .unwrap_or_default();
+    while let Some((entity, direct)) = stack.pop() {
+        if let Ok(c) = children.get(entity) {
+            stack.extend(c.iter().map(|e| (*e, false)));
+        }
+        let (transform, sprite, text, coords, cursor, game_over) = match nodes.get_mut(entity) {
+            Ok(n) => n,
+            Err(_) => continue,
+        };
+        let spans_board = direct && coords.is_none() && cursor.is_none();
+        if let Some(mut transform) = transform.filter(|_| direct) {
+            if game_over.is_some() {
+                transform.translation.x = middle_pos.x;
+                transform.translation.y = middle_pos.y;
+            } else {
+                transform.translation.x *= ratio;
+                transform.translation.y *= ratio;
+            }
+        }
+        if let Some(mut sprite) = sprite {
+            sprite.custom_size = sprite.custom_size.map(|size| {
+                if spans_board {
+                    board_size
+                } else {
+                    size * (size.x + delta) / size.x
+                }
+            });
+        }
+        if let Some(mut text) = text.filter(|_| game_over.is_none()) {
+            for section in text.sections.iter_mut() {
+                section.style.font_size += delta;
+            }
+        }
+    }
+}
@@ -3,6 +3,20 @@ use crate::{Board, BoardAssets, events::TileMarkEvent};
 use bevy::log;
 use bevy::prelude::*;
 
+/// Flag sprite, spawned as a child of a tile cover
+pub fn flag_bundle(board_assets: &BoardAssets, size: f32) -> SpriteBundle {
+    SpriteBundle {
+        texture: board_assets.flag_material.texture.clone(),
+        sprite: Sprite {
+            custom_size: Some(Vec2::splat(size)),
+            color: board_assets.flag_material.color,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(0., 0., 1.),
+        ..Default::default()
+    }
+}
+
 pub fn mark_tiles(
     mut commands: Commands,
     mut board: ResMut<Board>,
@@ -11,20 +25,12 @@ pub fn mark_tiles(
     query: Query<&Children>,
 ) {
     for event in tile_mark_event_rdr.iter() {
-        if let Some((entity, mark)) = board.try_toggle_mark(&event.0) {
+        // Chunk rendered boards have no cover entities
+        if let Some((Some(entity), mark)) = board.try_toggle_mark(&event.0) {
             if mark {
                 commands.entity(entity).with_children(|parent| {
                     parent
-                        .spawn(SpriteBundle {
-                            texture: board_assets.flag_material.texture.clone(),
-                            sprite: Sprite {
-                                custom_size: Some(Vec2::splat(board.tile_size)),
-                                color: board_assets.flag_material.color,
-                                ..Default::default()
-                            },
-                            transform: Transform::from_xyz(0., 0., 1.),
-                            ..Default::default()
-                        })
+                        .spawn(flag_bundle(&board_assets, board.tile_size))
                         .insert(Name::new("Flag"));
                 });
             } else {
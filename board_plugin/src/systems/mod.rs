@@ -2,4 +2,15 @@ pub mod input;
 pub mod uncover;
 pub mod mark;
 pub mod fail;
-pub mod completed;
\ No newline at end of file
+pub mod completed;
+pub mod overlay;
+pub mod status;
+pub mod hud;
+pub mod high_scores;
+pub mod replay;
+pub mod save;
+pub mod undo;
+pub mod hint;
+pub mod camera;
+pub mod layout;
+pub mod chunks;
\ No newline at end of file
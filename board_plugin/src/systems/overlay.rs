@@ -0,0 +1,34 @@
+// overlay.rs
+use crate::probability::bomb_probabilities;
+use crate::resources::ProbabilityOverlay;
+use crate::solver::{Solver, SOLVE_NODE_BUDGET};
+use crate::{Board, BoardAssets};
+use bevy::prelude::*;
+
+pub fn probability_overlay(
+    board: Res<Board>,
+    overlay: Res<ProbabilityOverlay>,
+    board_assets: Res<BoardAssets>,
+    mut sprites: Query<&mut Sprite>,
+) {
+    // Probabilities are computed only while visible, hiding the overlay restores the covers once
+    let needs_update = overlay.is_changed() || (overlay.enabled && board.is_changed());
+    if !needs_update {
+        return;
+    }
+    let probabilities = overlay
+        .enabled
+        .then(|| {
+            let solver = Solver::from_game(&board.game).with_node_budget(SOLVE_NODE_BUDGET);
+            bomb_probabilities(&solver)
+        })
+        .flatten();
+    for (coords, entity) in board.cover_entities() {
+        if let Ok(mut sprite) = sprites.get_mut(entity) {
+            sprite.color = match probabilities.as_ref().and_then(|p| p.get(&coords)) {
+                Some(probability) => board_assets.probability_color(*probability),
+                None => board_assets.covered_tile_material.color,
+            };
+        }
+    }
+}
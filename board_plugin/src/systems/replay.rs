@@ -0,0 +1,95 @@
+// replay.rs
+use crate::events::{
+    RedoEvent, SaveReplayEvent, TileChordEvent, TileMarkEvent, TileTriggerEvent, UndoEvent,
+};
+use crate::resources::{
+    BoardOptions, RecordedAction, ReplayAction, ReplayPlayback, ReplayRecorder,
+};
+use crate::{Board, Coordinates};
+use bevy::log;
+use bevy::prelude::*;
+
+/// Records the tile events of the current board, starting over on every new board
+#[allow(clippy::too_many_arguments)]
+pub fn record_actions(
+    time: Res<Time>,
+    board: Res<Board>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut tile_trigger_rdr: EventReader<TileTriggerEvent>,
+    mut tile_mark_rdr: EventReader<TileMarkEvent>,
+    mut tile_chord_rdr: EventReader<TileChordEvent>,
+    mut undo_rdr: EventReader<UndoEvent>,
+    mut redo_rdr: EventReader<RedoEvent>,
+) {
+    if board.is_added() {
+        recorder.start(board.game.clone());
+    } else {
+        recorder.elapsed += time.delta();
+    }
+    let time = recorder.elapsed;
+    let actions = tile_trigger_rdr
+        .iter()
+        .map(|e| (ReplayAction::Reveal, e.0))
+        .chain(tile_mark_rdr.iter().map(|e| (ReplayAction::Flag, e.0)))
+        .chain(tile_chord_rdr.iter().map(|e| (ReplayAction::Chord, e.0)))
+        .chain(undo_rdr.iter().map(|_| (ReplayAction::Undo, Coordinates::default())))
+        .chain(redo_rdr.iter().map(|_| (ReplayAction::Redo, Coordinates::default())));
+    for (action, coords) in actions {
+        recorder.actions.push(RecordedAction {
+            time,
+            action,
+            coords,
+        });
+    }
+}
+
+/// Writes the replay of the current board on `SaveReplayEvent`
+pub fn save_replay(
+    recorder: Res<ReplayRecorder>,
+    board_options: Option<Res<BoardOptions>>,
+    mut save_replay_rdr: EventReader<SaveReplayEvent>,
+) {
+    for event in save_replay_rdr.iter() {
+        let options = board_options
+            .as_ref()
+            .map_or_else(BoardOptions::default, |o| o.as_ref().clone());
+        let replay = match recorder.replay(options) {
+            None => continue,
+            Some(r) => r,
+        };
+        match replay.save(&event.0) {
+            Ok(()) => log::info!("Saved replay to {}", event.0.display()),
+            Err(e) => log::error!("Failed to save {}: {}", event.0.display(), e),
+        }
+    }
+}
+
+/// Sends the recorded actions of the `ReplayPlayback` when their time comes
+#[allow(clippy::too_many_arguments)]
+pub fn feed_replay(
+    time: Res<Time>,
+    board: Res<Board>,
+    playback: Option<ResMut<ReplayPlayback>>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
+    mut undo_ewr: EventWriter<UndoEvent>,
+    mut redo_ewr: EventWriter<RedoEvent>,
+) {
+    let mut playback = match playback {
+        None => return,
+        Some(p) => p,
+    };
+    if playback.rebuilding && board.is_added() {
+        playback.rebuilding = false;
+    }
+    for action in playback.advance(time.delta()) {
+        match action.action {
+            ReplayAction::Reveal => tile_trigger_ewr.send(TileTriggerEvent(action.coords)),
+            ReplayAction::Flag => tile_mark_ewr.send(TileMarkEvent(action.coords)),
+            ReplayAction::Chord => tile_chord_ewr.send(TileChordEvent(action.coords)),
+            ReplayAction::Undo => undo_ewr.send(UndoEvent),
+            ReplayAction::Redo => redo_ewr.send(RedoEvent),
+        }
+    }
+}
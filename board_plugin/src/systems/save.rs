@@ -0,0 +1,28 @@
+// save.rs
+use crate::events::SaveBoardEvent;
+use crate::resources::{BoardOptions, GameTimer, SavedGame};
+use crate::Board;
+use bevy::log;
+use bevy::prelude::*;
+
+/// Writes the current game on `SaveBoardEvent`
+pub fn save_board(
+    board: Res<Board>,
+    board_options: Option<Res<BoardOptions>>,
+    timer: Res<GameTimer>,
+    mut save_board_rdr: EventReader<SaveBoardEvent>,
+) {
+    for event in save_board_rdr.iter() {
+        let saved_game = SavedGame {
+            game: board.game.clone(),
+            options: board_options
+                .as_ref()
+                .map_or_else(BoardOptions::default, |o| o.as_ref().clone()),
+            elapsed: timer.elapsed,
+        };
+        match saved_game.save(&event.0) {
+            Ok(()) => log::info!("Saved game to {}", event.0.display()),
+            Err(e) => log::error!("Failed to save {}: {}", event.0.display(), e),
+        }
+    }
+}
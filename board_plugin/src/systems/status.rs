@@ -0,0 +1,27 @@
+// status.rs
+use crate::events::GameStatusChangedEvent;
+use crate::game::Outcome;
+use crate::resources::GameStatus;
+use crate::Board;
+use bevy::log;
+use bevy::prelude::*;
+
+/// Keeps the `GameStatus` in sync with the board game
+pub fn update_status(
+    board: Res<Board>,
+    mut status: ResMut<GameStatus>,
+    mut status_changed_ewr: EventWriter<GameStatusChangedEvent>,
+) {
+    let current = match board.game.outcome() {
+        Some(Outcome::Won) => GameStatus::Won,
+        Some(Outcome::Lost) => GameStatus::Lost,
+        None if board.game.has_started() => GameStatus::Playing,
+        None => GameStatus::Ready,
+    };
+    // A new board always starts a new round
+    if board.is_added() || *status != current {
+        log::info!("Game status: {:?}", current);
+        *status = current;
+        status_changed_ewr.send(GameStatusChangedEvent(current));
+    }
+}
@@ -1,62 +1,156 @@
 // systems/uncover.rs
-use crate::events::{BoardCompletedEvent, BombExplosionEvent, TileTriggerEvent};
-use crate::{Board, Bomb, BombNeighbor, Coordinates, Uncover};
+use crate::events::{BoardCompletedEvent, BombExplosionEvent, TileChordEvent, TileTriggerEvent};
+use crate::game::{Outcome, Reveal};
+use crate::resources::{BoardAssets, BoardOptions, TileMap, Topology};
+use crate::{Board, Coordinates, Uncover, UncoverDelay};
 use bevy::log;
 use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Cover sprite of a `topology` tile, spawned as a child of a covered tile
+pub fn cover_bundle(board_assets: &BoardAssets, topology: Topology, size: f32) -> SpriteBundle {
+    SpriteBundle {
+        sprite: Sprite {
+            custom_size: Some(topology.sprite_size(size)),
+            color: board_assets.covered_tile_material.color,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(0., 0., 2.),
+        texture: topology.sprite_texture(&board_assets.covered_tile_material),
+        ..Default::default()
+    }
+}
 
 pub fn trigger_event_handler(
     mut commands: Commands,
-    board: Res<Board>,
+    mut board: ResMut<Board>,
+    board_options: Option<Res<BoardOptions>>,
     mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut board_completed_event_wr: EventWriter<BoardCompletedEvent>,
+    mut bomb_explosion_event_wr: EventWriter<BombExplosionEvent>,
 ) {
     for trigger_event in tile_trigger_evr.iter() {
-        if let Some(entity) = board.tile_to_uncover(&trigger_event.0) {
-            commands.entity(*entity).insert(Uncover);
-        }
+        let reveal = board.game.reveal(trigger_event.0);
+        apply_reveal(
+            &mut commands,
+            &mut board,
+            reveal,
+            board_options.as_ref().and_then(|o| o.reveal_ring_delay),
+            &mut board_completed_event_wr,
+            &mut bomb_explosion_event_wr,
+        );
     }
 }
 
-pub fn uncover_tiles(
+pub fn chord_event_handler(
     mut commands: Commands,
     mut board: ResMut<Board>,
-    children: Query<(Entity, &Parent), With<Uncover>>,
-    parents: Query<(&Coordinates, Option<&Bomb>, Option<&BombNeighbor>)>,
+    board_options: Option<Res<BoardOptions>>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
     mut board_completed_event_wr: EventWriter<BoardCompletedEvent>,
     mut bomb_explosion_event_wr: EventWriter<BombExplosionEvent>,
 ) {
-    for (entity, parent) in children.iter() {
-        // we destroy the tile cover entity
-        commands.entity(entity).despawn_recursive();
+    for chord_event in tile_chord_evr.iter() {
+        let reveal = board.game.chord(chord_event.0);
+        apply_reveal(
+            &mut commands,
+            &mut board,
+            reveal,
+            board_options.as_ref().and_then(|o| o.reveal_ring_delay),
+            &mut board_completed_event_wr,
+            &mut bomb_explosion_event_wr,
+        );
+    }
+}
 
-        let (coords, bomb, bomb_counter) = match parents.get(parent.get()) {
-            Ok(v) => v,
-            Err(e) => {
-                log::error!("{}", e);
-                continue;
-            }
+/// Marks the covers of the revealed tiles, they are removed by `uncover_tiles`, and sends
+/// the game outcome events.
+///
+/// With a `ring_delay` the covers away from the first revealed tile wait for their ring
+pub(crate) fn apply_reveal(
+    commands: &mut Commands,
+    board: &mut Board,
+    reveal: Reveal,
+    ring_delay: Option<Duration>,
+    board_completed_event_wr: &mut EventWriter<BoardCompletedEvent>,
+    bomb_explosion_event_wr: &mut EventWriter<BombExplosionEvent>,
+) {
+    let rings = match ring_delay {
+        Some(_) => reveal_rings(board.game.tile_map(), &reveal.revealed),
+        None => HashMap::default(),
+    };
+    board.changed_tiles.extend(reveal.revealed.iter().copied());
+    for coords in reveal.revealed.iter() {
+        let entity = match board.take_cover(*coords) {
+            None => continue,
+            Some(e) => e,
         };
-
-        // We remove the entity from the board covered tile map
-        match board.try_uncover_tile(coords) {
-            None => log::debug!("Tried to uncover an already uncovered tile"),
-            Some(e) => log::debug!("Uncovered tile {} (entity: {:?})", coords, e),
+        match (ring_delay, rings.get(coords)) {
+            (Some(delay), Some(ring)) if *ring > 0 => {
+                let timer = Timer::new(delay * *ring, TimerMode::Once);
+                commands.entity(entity).insert(UncoverDelay(timer));
+            }
+            _ => {
+                commands.entity(entity).insert(Uncover);
+            }
         }
-        if bomb.is_some() {
+    }
+    match reveal.outcome {
+        Some(Outcome::Lost) => {
             log::info!("Boom !");
             bomb_explosion_event_wr.send(BombExplosionEvent);
         }
-        // If the tile is empty..
-        else if bomb_counter.is_none() {
-            // .. We propagate the uncovering by adding the `Uncover` component to adjacent tiles
-            // which will then be removed next frame
-            for entity in board.adjacent_covered_tiles(*coords) {
-                commands.entity(entity).insert(Uncover);
-            }
-        };
-
-        if board.is_completed() {
+        Some(Outcome::Won) => {
             log::info!("Board completed");
             board_completed_event_wr.send(BoardCompletedEvent);
         }
+        None => (),
+    }
+}
+
+/// Ring of each `revealed` tile, its distance to the first one through the revealed tiles.
+///
+/// A tile out of reach starts its own rings, like the disconnected regions of a chord
+fn reveal_rings(tile_map: &TileMap, revealed: &[Coordinates]) -> HashMap<Coordinates, u32> {
+    let mut rings: HashMap<Coordinates, u32> = revealed.iter().map(|c| (*c, u32::MAX)).collect();
+    for start in revealed {
+        if rings[start] != u32::MAX {
+            continue;
+        }
+        rings.insert(*start, 0);
+        let mut queue = VecDeque::from([*start]);
+        while let Some(coords) = queue.pop_front() {
+            let ring = rings[&coords] + 1;
+            for neighbor in tile_map.safe_square_at(coords) {
+                if rings.get(&neighbor).is_some_and(|r| *r > ring) {
+                    rings.insert(neighbor, ring);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+    rings
+}
+
+/// Uncovers the delayed covers once their ring is reached
+pub fn delay_uncover(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut covers: Query<(Entity, &mut UncoverDelay)>,
+) {
+    for (entity, mut delay) in covers.iter_mut() {
+        if delay.0.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<UncoverDelay>().insert(Uncover);
+        }
+    }
+}
+
+pub fn uncover_tiles(mut commands: Commands, covers: Query<Entity, With<Uncover>>) {
+    for entity in covers.iter() {
+        // we destroy the tile cover entity
+        commands.entity(entity).despawn_recursive();
+        log::debug!("Uncovered tile cover {:?}", entity);
     }
 }
@@ -0,0 +1,107 @@
+// undo.rs
+use crate::events::{BoardCompletedEvent, BombExplosionEvent, RedoEvent, UndoEvent};
+use crate::game::Reveal;
+use crate::resources::{BoardAssets, BoardOptions};
+use crate::systems::mark::flag_bundle;
+use crate::systems::uncover::{apply_reveal, cover_bundle};
+use crate::{Board, Coordinates, GameOver};
+use bevy::log;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Undoes and redoes the board actions, if `BoardOptions::undo` is set
+#[allow(clippy::too_many_arguments)]
+pub fn undo_event_handler(
+    mut commands: Commands,
+    mut board: ResMut<Board>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    tiles: Query<(Entity, &Coordinates, &Sprite)>,
+    children: Query<&Children>,
+    game_over: Query<Entity, With<GameOver>>,
+    mut undo_rdr: EventReader<UndoEvent>,
+    mut redo_rdr: EventReader<RedoEvent>,
+    mut board_completed_ewr: EventWriter<BoardCompletedEvent>,
+    mut bomb_explosion_ewr: EventWriter<BombExplosionEvent>,
+) {
+    let ring_delay = match board_options {
+        Some(o) if o.undo => o.reveal_ring_delay,
+        _ => return,
+    };
+    for _ in undo_rdr.iter() {
+        let step = match board.game.undo() {
+            None => continue,
+            Some(s) => s,
+        };
+        log::info!("Undoing {} uncovered tiles", step.revealed.len());
+        let changed = step.revealed.iter().chain(step.flags.iter()).copied();
+        board.changed_tiles.extend(changed);
+        if step.before.is_none() && step.after.is_some() {
+            for entity in game_over.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        let revealed: HashSet<Coordinates> = step.revealed.iter().copied().collect();
+        let topology = board.game.tile_map().topology();
+        for (entity, coords, sprite) in tiles.iter().filter(|(_, c, _)| revealed.contains(c)) {
+            let size = sprite.custom_size.unwrap_or(Vec2::splat(board.tile_size)).x;
+            let cover = commands
+                .spawn(cover_bundle(&board_assets, topology, size))
+                .insert(Name::new("Tile Cover"))
+                .id();
+            commands.entity(entity).add_child(cover);
+            board.set_cover(*coords, cover);
+        }
+        for coords in &step.flags {
+            update_flag(&mut commands, &board, &board_assets, &children, *coords);
+        }
+    }
+    for _ in redo_rdr.iter() {
+        let step = match board.game.redo() {
+            None => continue,
+            Some(s) => s,
+        };
+        log::info!("Redoing {} uncovered tiles", step.revealed.len());
+        board.changed_tiles.extend(step.flags.iter().copied());
+        for coords in step.flags.iter().filter(|c| !step.revealed.contains(c)) {
+            update_flag(&mut commands, &board, &board_assets, &children, *coords);
+        }
+        let reveal = Reveal {
+            outcome: if step.before.is_none() { step.after } else { None },
+            revealed: step.revealed,
+        };
+        apply_reveal(
+            &mut commands,
+            &mut board,
+            reveal,
+            ring_delay,
+            &mut board_completed_ewr,
+            &mut bomb_explosion_ewr,
+        );
+    }
+}
+
+/// Spawns or removes the flag of a covered tile to match the game
+fn update_flag(
+    commands: &mut Commands,
+    board: &Board,
+    board_assets: &BoardAssets,
+    children: &Query<&Children>,
+    coords: Coordinates,
+) {
+    let cover = match board.cover(coords) {
+        None => return,
+        Some(e) => e,
+    };
+    if board.game.is_flagged(coords) {
+        commands.entity(cover).with_children(|parent| {
+            parent
+                .spawn(flag_bundle(board_assets, board.tile_size))
+                .insert(Name::new("Flag"));
+        });
+    } else if let Ok(flags) = children.get(cover) {
+        for flag in flags.iter() {
+            commands.entity(*flag).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use board_plugin::resources::{
+    BoardOptions, BoardOptionsError, BombPlacement, ChordInput, RenderMode, Topology,
+};
+
+#[test]
+fn presets_are_valid() {
+    for options in [
+        BoardOptions::beginner(),
+        BoardOptions::intermediate(),
+        BoardOptions::expert(),
+    ] {
+        assert_eq!(options.validate(), Ok(()));
+    }
+}
+
+#[test]
+fn custom_boards_are_validated() {
+    assert!(BoardOptions::custom((10, 8), 79, false).is_ok());
+    assert_eq!(
+        BoardOptions::custom((10, 8), 80, false).err(),
+        Some(BoardOptionsError::BombCount { max: 79 })
+    );
+    assert_eq!(
+        BoardOptions::custom((10, 8), 72, true).err(),
+        Some(BoardOptionsError::BombCount { max: 71 })
+    );
+    assert_eq!(
+        BoardOptions::custom((10, 8), 0, false).err(),
+        Some(BoardOptionsError::BombCount { max: 79 })
+    );
+    assert_eq!(
+        BoardOptions::custom((1, 8), 1, false).err(),
+        Some(BoardOptionsError::MapSize { max: 100 })
+    );
+    assert_eq!(
+        BoardOptions::custom((10, 101), 1, false).err(),
+        Some(BoardOptionsError::MapSize { max: 100 })
+    );
+}
+
+#[test]
+fn chunk_boards_can_be_larger() {
+    let chunks = BoardOptions {
+        map_size: (1000, 600),
+        bomb_count: 60_000,
+        render_mode: RenderMode::Chunks { size: 32 },
+        ..Default::default()
+    };
+    assert_eq!(chunks.validate(), Ok(()));
+    // More tiles than bombs can be counted
+    assert_eq!(
+        BoardOptions {
+            bomb_count: 0,
+            ..chunks.clone()
+        }
+        .validate(),
+        Err(BoardOptionsError::BombCount { max: u16::MAX })
+    );
+    assert_eq!(
+        BoardOptions {
+            map_size: (1001, 600),
+            ..chunks.clone()
+        }
+        .validate(),
+        Err(BoardOptionsError::MapSize { max: 1000 })
+    );
+    // Hex boards fall back to sprites
+    let hex = BoardOptions {
+        topology: Topology::Hex,
+        ..chunks.clone()
+    };
+    assert_eq!(hex.validate(), Err(BoardOptionsError::MapSize { max: 100 }));
+    let sprites = BoardOptions {
+        render_mode: RenderMode::Sprites,
+        ..chunks
+    };
+    assert_eq!(
+        sprites.validate(),
+        Err(BoardOptionsError::MapSize { max: 100 })
+    );
+}
+
+#[test]
+fn options_without_later_fields_load() {
+    // Options written before the bomb placement, the no guess generation and chording existed
+    let options: BoardOptions = ron::from_str(
+        "(
+            map_size: (9, 9),
+            bomb_count: 10,
+            position: Custom((0.0, 0.0, 0.0)),
+            tile_size: Fixed(20.0),
+            tile_padding: 1.0,
+            safe_start: true,
+            seed: Some(7),
+        )",
+    )
+    .unwrap();
+    assert_eq!(options.map_size, (9, 9));
+    assert_eq!(options.seed, Some(7));
+    assert_eq!(options.bomb_placement, BombPlacement::OnCreation);
+    assert!(options.no_guess.is_none());
+    assert_eq!(options.chord_input, ChordInput::MiddleClick);
+}
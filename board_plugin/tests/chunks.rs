@@ -0,0 +1,89 @@
+mod common;
+
+use bevy::asset::AssetPlugin;
+use bevy::prelude::*;
+use bevy::sprite::Mesh2dHandle;
+use board_plugin::components::{Coordinates, TileChunk};
+use board_plugin::events::{TileMarkEvent, TileTriggerEvent};
+use board_plugin::resources::{Board, BoardOptions, RenderMode, Tile, TileSize};
+
+/// Headless app running a 70x50 board in chunks of 32 tiles, with the mesh assets but no renderer
+fn app() -> App {
+    let mut app = common::app(BoardOptions {
+        map_size: (70, 50),
+        bomb_count: 200,
+        tile_size: TileSize::Fixed(10.),
+        seed: Some(3),
+        render_mode: RenderMode::Chunks { size: 32 },
+        ..Default::default()
+    });
+    app.add_plugin(AssetPlugin::default())
+        .add_asset::<Mesh>()
+        .add_asset::<Image>()
+        .add_asset::<ColorMaterial>();
+    app.update();
+    app.update();
+    app
+}
+
+/// Texture coordinates of the chunk meshes, by chunk origin
+fn chunk_uvs(app: &mut App) -> Vec<(Coordinates, Vec<[f32; 2]>)> {
+    let meshes: Vec<_> = app
+        .world
+        .query::<(&TileChunk, &Mesh2dHandle)>()
+        .iter(&app.world)
+        .map(|(chunk, handle)| (chunk.origin, handle.0.clone()))
+        .collect();
+    let assets = app.world.resource::<Assets<Mesh>>();
+    let mut uvs: Vec<_> = meshes
+        .into_iter()
+        .map(|(origin, handle)| {
+            let mesh = assets.get(&handle).unwrap();
+            let uvs = match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+                Some(bevy::render::mesh::VertexAttributeValues::Float32x2(uvs)) => uvs.clone(),
+                _ => panic!("missing texture coordinates"),
+            };
+            (origin, uvs)
+        })
+        .collect();
+    uvs.sort_by_key(|(origin, _)| *origin);
+    uvs
+}
+
+#[test]
+fn chunks_draw_the_board_and_follow_the_game() {
+    let mut app = app();
+    assert_eq!(app.world.query::<&Coordinates>().iter(&app.world).count(), 0);
+    let before = chunk_uvs(&mut app);
+    assert_eq!(before.len(), 6);
+    // A 70 tiles line is split in 32, 32 and 6 tiles
+    assert_eq!(before[4].0, Coordinates { x: 64, y: 0 });
+    assert_eq!(before[4].1.len(), 6 * 32 * 4);
+
+    let game = &app.world.resource::<Board>().game;
+    let (safe, _) = game
+        .tile_map()
+        .iter()
+        .find(|(c, tile)| !tile.is_bomb() && c.x < 32 && c.y < 32 && **tile != Tile::Empty)
+        .unwrap();
+    let flagged = Coordinates { x: 69, y: 49 };
+    app.world.send_event(TileTriggerEvent(safe));
+    app.world.send_event(TileMarkEvent(flagged));
+    app.update();
+    app.update();
+
+    let game = &app.world.resource::<Board>().game;
+    assert!(!game.is_covered(safe) && game.is_flagged(flagged));
+    let after = chunk_uvs(&mut app);
+    // Only the chunks of the changed tiles are drawn again
+    let changed: Vec<_> = before
+        .iter()
+        .zip(after.iter())
+        .filter(|(b, a)| b.1 != a.1)
+        .map(|(_, a)| a.0)
+        .collect();
+    assert_eq!(
+        changed,
+        vec![Coordinates { x: 0, y: 0 }, Coordinates { x: 64, y: 32 }]
+    );
+}
@@ -0,0 +1,51 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::window::WindowPlugin;
+use board_plugin::resources::{BoardAssets, BoardOptions, HudStyle, SpriteMaterial};
+use board_plugin::BoardPlugin;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+enum TestState {
+    InGame,
+}
+
+/// Headless app running a board of `options`, without window nor rendering.
+///
+/// The board is created on the first update
+pub fn app(options: BoardOptions) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(WindowPlugin {
+            add_primary_window: false,
+            exit_on_all_closed: false,
+            ..Default::default()
+        })
+        .add_plugin(InputPlugin)
+        .add_state(TestState::InGame)
+        .add_plugin(BoardPlugin {
+            running_state: TestState::InGame,
+        })
+        .insert_resource(options)
+        .insert_resource(BoardAssets {
+            label: "Test".to_string(),
+            board_material: SpriteMaterial::default(),
+            tile_material: SpriteMaterial::default(),
+            covered_tile_material: SpriteMaterial::default(),
+            bomb_counter_font: Default::default(),
+            bomb_counter_colors: BoardAssets::default_colors(),
+            flag_material: SpriteMaterial::default(),
+            bomb_material: SpriteMaterial::default(),
+            fail_material: SpriteMaterial::default(),
+            fail_cover_board_material: SpriteMaterial::default(),
+            game_over_font_size: 10.,
+            probability_colors: BoardAssets::default_probability_colors(),
+            hud: HudStyle {
+                font: Default::default(),
+                font_size: 10.,
+                color: Color::WHITE,
+                position: Default::default(),
+            },
+            cursor_material: SpriteMaterial::default(),
+        });
+    app
+}
@@ -0,0 +1,248 @@
+use board_plugin::components::Coordinates;
+use board_plugin::game::{Game, Outcome};
+use board_plugin::resources::{BoardOptions, BombPlacement, SavedGame, Tile, TileMap, Topology};
+use std::time::Duration;
+
+fn tiles(tile_map: &TileMap) -> impl Iterator<Item = (Coordinates, Tile)> + '_ {
+    tile_map.iter().map(|(coords, tile)| (coords, *tile))
+}
+
+#[test]
+fn revealing_every_safe_tile_wins() {
+    let mut game = Game::new(
+        (16, 16),
+        40,
+        7,
+        BombPlacement::OnCreation,
+        None,
+        Topology::Square,
+    );
+    let safe: Vec<_> = tiles(game.tile_map())
+        .filter(|(_, tile)| !tile.is_bomb())
+        .map(|(c, _)| c)
+        .collect();
+    let mut outcome = None;
+    for coords in safe {
+        outcome = outcome.or(game.reveal(coords).outcome);
+    }
+    assert_eq!(outcome, Some(Outcome::Won));
+    assert_eq!(game.covered_tiles().count(), 40);
+}
+
+#[test]
+fn revealing_a_bomb_loses() {
+    let mut game = Game::new(
+        (16, 16),
+        40,
+        7,
+        BombPlacement::OnCreation,
+        None,
+        Topology::Square,
+    );
+    let (bomb, _) = tiles(game.tile_map()).find(|(_, t)| t.is_bomb()).unwrap();
+
+    let reveal = game.reveal(bomb);
+    assert_eq!(reveal.revealed, vec![bomb]);
+    assert_eq!(reveal.outcome, Some(Outcome::Lost));
+    // The game is over
+    assert!(game.reveal(Coordinates { x: 0, y: 0 }).revealed.is_empty());
+}
+
+#[test]
+fn empty_tiles_propagate_and_flags_block() {
+    let mut game = Game::new(
+        (16, 16),
+        40,
+        7,
+        BombPlacement::OnCreation,
+        None,
+        Topology::Square,
+    );
+    let (empty, _) = tiles(game.tile_map())
+        .find(|(_, t)| *t == Tile::Empty)
+        .unwrap();
+
+    assert_eq!(game.toggle_flag(empty), Some(true));
+    assert!(game.reveal(empty).revealed.is_empty());
+    assert_eq!(game.toggle_flag(empty), Some(false));
+
+    let reveal = game.reveal(empty);
+    assert!(reveal.revealed.len() > 1);
+    assert!(reveal.revealed.iter().all(|c| !game.is_covered(*c)));
+    assert_eq!(game.toggle_flag(empty), None);
+}
+
+#[test]
+fn first_click_is_safe() {
+    for seed in 0..20 {
+        let start = Coordinates { x: 3, y: 3 };
+        let mut game = Game::new(
+            (8, 8),
+            50,
+            seed,
+            BombPlacement::FirstClick { safe_square: true },
+            None,
+            Topology::Square,
+        );
+        assert!(game.has_pending_bombs());
+
+        assert_ne!(game.reveal(start).outcome, Some(Outcome::Lost));
+        assert!(!game.has_pending_bombs());
+        assert_eq!(game.tile_map().tile_at(start), Some(&Tile::Empty));
+    }
+}
+
+#[test]
+fn chord_uncovers_neighbors_when_flags_match() {
+    let mut game = Game::new(
+        (16, 16),
+        40,
+        7,
+        BombPlacement::OnCreation,
+        None,
+        Topology::Square,
+    );
+    let (number, _) = tiles(game.tile_map())
+        .find(|(_, t)| matches!(t, Tile::BombNeighbor(_)))
+        .unwrap();
+    let neighbors: Vec<_> = game
+        .tile_map()
+        .safe_square_at(number)
+        .filter(|c| game.tile_map().tile_at(*c).is_some())
+        .collect();
+
+    game.reveal(number);
+    // Not enough flags
+    assert!(game.chord(number).revealed.is_empty());
+
+    let bombs: Vec<_> = neighbors
+        .iter()
+        .filter(|c| game.tile_map().is_bomb_at(**c))
+        .collect();
+    for c in bombs {
+        game.toggle_flag(*c);
+    }
+    let reveal = game.chord(number);
+    assert_eq!(reveal.outcome, None);
+    assert!(neighbors
+        .iter()
+        .all(|c| game.tile_map().is_bomb_at(*c) || !game.is_covered(*c)));
+}
+
+#[test]
+fn chord_with_wrong_flag_loses() {
+    let mut game = Game::new(
+        (16, 16),
+        40,
+        7,
+        BombPlacement::OnCreation,
+        None,
+        Topology::Square,
+    );
+    let (number, _) = tiles(game.tile_map())
+        .find(|(_, t)| *t == Tile::BombNeighbor(1))
+        .unwrap();
+    let wrong = game
+        .tile_map()
+        .safe_square_at(number)
+        .find(|c| game.tile_map().tile_at(*c).is_some_and(|t| !t.is_bomb()))
+        .unwrap();
+
+    game.reveal(number);
+    game.toggle_flag(wrong);
+    assert_eq!(game.chord(number).outcome, Some(Outcome::Lost));
+}
+
+#[test]
+fn saved_games_resume_where_they_stopped() {
+    let mut game = Game::new(
+        (16, 16),
+        40,
+        11,
+        BombPlacement::FirstClick { safe_square: true },
+        None,
+        Topology::Square,
+    );
+    game.reveal(Coordinates { x: 8, y: 8 });
+    let bomb = tiles(game.tile_map())
+        .find(|(_, tile)| tile.is_bomb())
+        .map(|(c, _)| c)
+        .unwrap();
+    game.toggle_flag(bomb);
+    let saved_game = SavedGame {
+        game,
+        options: BoardOptions::intermediate(),
+        elapsed: Duration::from_millis(12_345),
+    };
+    let loaded: SavedGame = ron::from_str(&ron::to_string(&saved_game).unwrap()).unwrap();
+
+    assert_eq!(loaded.elapsed, saved_game.elapsed);
+    assert_eq!(loaded.options.map_size, (16, 16));
+    assert_eq!(loaded.game.seed(), 11);
+    assert!(!loaded.game.has_pending_bombs());
+    assert_eq!(
+        tiles(loaded.game.tile_map()).collect::<Vec<_>>(),
+        tiles(saved_game.game.tile_map()).collect::<Vec<_>>()
+    );
+    let mut covered: Vec<_> = loaded.game.covered_tiles().collect();
+    let mut expected: Vec<_> = saved_game.game.covered_tiles().collect();
+    covered.sort();
+    expected.sort();
+    assert_eq!(covered, expected);
+    assert!(loaded.game.is_flagged(bomb));
+    assert_eq!(loaded.game.flag_count(), 1);
+}
+
+#[test]
+fn undo_restores_covers_flags_and_outcome() {
+    let mut game = Game::new(
+        (16, 16),
+        40,
+        5,
+        BombPlacement::OnCreation,
+        None,
+        Topology::Square,
+    );
+    let empty = tiles(game.tile_map())
+        .find(|(_, tile)| *tile == Tile::Empty)
+        .map(|(c, _)| c)
+        .unwrap();
+    let bomb = tiles(game.tile_map())
+        .find(|(_, tile)| tile.is_bomb())
+        .map(|(c, _)| c)
+        .unwrap();
+    // Actions are only recorded with undo on
+    let mut without_undo = game.clone();
+    without_undo.reveal(empty);
+    assert!(!without_undo.can_undo());
+    game.set_undo(true);
+
+    // A flag wiped by the flood fill comes back with the cover
+    let flooded = game.clone().reveal(empty).revealed;
+    let flagged = *flooded.last().unwrap();
+    game.toggle_flag(flagged);
+    let revealed = game.reveal(empty).revealed;
+    assert!(!game.is_flagged(flagged) && !game.is_covered(flagged));
+    game.reveal(bomb);
+    assert_eq!(game.outcome(), Some(Outcome::Lost));
+
+    let step = game.undo().unwrap();
+    assert_eq!(step.revealed, vec![bomb]);
+    assert_eq!(game.outcome(), None);
+    assert!(game.is_covered(bomb));
+    let step = game.undo().unwrap();
+    assert_eq!(step.revealed, revealed);
+    assert!(revealed.iter().all(|c| game.is_covered(*c)));
+    assert!(game.is_flagged(flagged));
+    game.undo().unwrap();
+    assert!(!game.is_flagged(flagged));
+    assert!(game.undo().is_none());
+
+    game.redo().unwrap();
+    game.redo().unwrap();
+    assert!(!game.is_covered(flagged) && !game.is_flagged(flagged));
+    // A new action drops the undone ones
+    game.toggle_flag(bomb);
+    assert!(!game.can_redo());
+    assert_eq!(game.outcome(), None);
+}
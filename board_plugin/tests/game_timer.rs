@@ -0,0 +1,57 @@
+mod common;
+
+use bevy::prelude::*;
+use board_plugin::events::{NewBoardEvent, TileTriggerEvent};
+use board_plugin::resources::{Board, BoardOptions, GameStatus, GameTimer, TileSize};
+use std::thread;
+use std::time::Duration;
+
+/// Headless app running a 10x10 board opened by a safe start, without window nor rendering
+fn app() -> App {
+    let mut app = common::app(BoardOptions {
+        map_size: (10, 10),
+        bomb_count: 10,
+        tile_size: TileSize::Fixed(10.),
+        seed: Some(1),
+        safe_start: true,
+        ..Default::default()
+    });
+    frames(&mut app, 3);
+    app
+}
+
+/// Runs `count` updates, letting some time pass before each of them
+fn frames(app: &mut App, count: usize) {
+    for _ in 0..count {
+        thread::sleep(Duration::from_millis(2));
+        app.update();
+    }
+}
+
+fn timer(app: &App) -> (Duration, bool) {
+    let timer = app.world.resource::<GameTimer>();
+    (timer.elapsed, timer.running)
+}
+
+#[test]
+fn timer_starts_on_the_first_move() {
+    let mut app = app();
+    // The safe start is not a move
+    assert_eq!(*app.world.resource::<GameStatus>(), GameStatus::Playing);
+    assert_eq!(timer(&app), (Duration::ZERO, false));
+
+    let game = &app.world.resource::<Board>().game;
+    let covered = game
+        .covered_tiles()
+        .find(|c| !game.tile_map().is_bomb_at(*c))
+        .unwrap();
+    app.world.send_event(TileTriggerEvent(covered));
+    frames(&mut app, 3);
+    let (elapsed, running) = timer(&app);
+    assert!(running && elapsed > Duration::ZERO);
+
+    // New boards wait for their first move again
+    app.world.send_event(NewBoardEvent(None));
+    frames(&mut app, 3);
+    assert_eq!(timer(&app), (Duration::ZERO, false));
+}
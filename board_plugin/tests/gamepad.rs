@@ -0,0 +1,74 @@
+mod common;
+
+use bevy::input::gamepad::{GamepadEventRaw, GamepadEventType, GamepadInfo};
+use bevy::prelude::*;
+use board_plugin::components::{Coordinates, Cursor};
+use board_plugin::resources::{Board, BoardOptions, BombPlacement, TileSize};
+
+const GAMEPAD: Gamepad = Gamepad { id: 0 };
+
+/// Headless app running a 10x10 board, without window nor rendering
+fn app() -> App {
+    let mut app = common::app(BoardOptions {
+        map_size: (10, 10),
+        bomb_count: 10,
+        tile_size: TileSize::Fixed(10.),
+        seed: Some(1),
+        bomb_placement: BombPlacement::FirstClick { safe_square: true },
+        ..Default::default()
+    });
+    app.update();
+    send(&mut app, GamepadEventType::Connected(GamepadInfo {
+        name: "Test pad".to_string(),
+    }));
+    app
+}
+
+fn send(app: &mut App, event_type: GamepadEventType) {
+    app.world.send_event(GamepadEventRaw::new(GAMEPAD, event_type));
+    app.update();
+}
+
+fn press(app: &mut App, button: GamepadButtonType) {
+    send(app, GamepadEventType::ButtonChanged(button, 1.));
+    send(app, GamepadEventType::ButtonChanged(button, 0.));
+}
+
+fn cursor(app: &mut App) -> Coordinates {
+    app.world.query::<&Cursor>().single(&app.world).coordinates
+}
+
+#[test]
+fn gamepad_moves_the_cursor_and_plays() {
+    let mut app = app();
+    assert_eq!(cursor(&mut app), Coordinates { x: 5, y: 5 });
+
+    // The first input shows the cursor
+    press(&mut app, GamepadButtonType::DPadRight);
+    assert_eq!(cursor(&mut app), Coordinates { x: 5, y: 5 });
+    press(&mut app, GamepadButtonType::DPadRight);
+    assert_eq!(cursor(&mut app), Coordinates { x: 6, y: 5 });
+    // Holding the stick moves once
+    send(&mut app, GamepadEventType::AxisChanged(GamepadAxisType::LeftStickY, 1.));
+    send(&mut app, GamepadEventType::AxisChanged(GamepadAxisType::LeftStickY, 0.9));
+    send(&mut app, GamepadEventType::AxisChanged(GamepadAxisType::LeftStickY, 0.));
+    assert_eq!(cursor(&mut app), Coordinates { x: 6, y: 6 });
+
+    let coords = Coordinates { x: 6, y: 6 };
+    press(&mut app, GamepadButtonType::East);
+    assert!(app.world.resource::<Board>().game.is_flagged(coords));
+    press(&mut app, GamepadButtonType::East);
+    assert!(!app.world.resource::<Board>().game.is_flagged(coords));
+    press(&mut app, GamepadButtonType::South);
+    let game = &app.world.resource::<Board>().game;
+    assert!(game.has_started() && !game.is_covered(coords));
+
+    // The hint points at a safe covered tile
+    press(&mut app, GamepadButtonType::North);
+    let hint = cursor(&mut app);
+    let game = &app.world.resource::<Board>().game;
+    assert!(game.is_covered(hint) && !game.tile_map().is_bomb_at(hint));
+
+    press(&mut app, GamepadButtonType::Start);
+    assert!(!app.world.resource::<Board>().game.has_started());
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use board_plugin::components::Coordinates;
+use board_plugin::events::PlayReplayEvent;
+use board_plugin::game::{Game, Outcome};
+use board_plugin::resources::{
+    Board, BoardOptions, BombPlacement, HighScore, HighScores, RecordedAction, Replay,
+    ReplayAction, ScoreKey, TileSize, Topology,
+};
+use std::thread;
+use std::time::Duration;
+
+fn score(secs: u64) -> HighScore {
+    HighScore {
+        time: Duration::from_secs(secs),
+        date: 0,
+        player: "Player".to_string(),
+        seed: secs,
+    }
+}
+
+#[test]
+fn scores_are_ranked_and_saved() {
+    let directory = std::env::temp_dir().join(format!("high_scores_{}", std::process::id()));
+    let key = ScoreKey::from(&BoardOptions::default());
+    let mut high_scores = HighScores::load(&directory);
+    assert_eq!(high_scores.record(key.clone(), score(30)), Some(0));
+    assert_eq!(high_scores.record(key.clone(), score(10)), Some(0));
+    assert_eq!(high_scores.record(key.clone(), score(20)), Some(1));
+    for _ in 0..10 {
+        high_scores.record(key.clone(), score(5));
+    }
+    assert_eq!(high_scores.record(key.clone(), score(40)), None);
+    high_scores.save().unwrap();
+
+    let loaded = HighScores::load(&directory);
+    let times: Vec<u64> = loaded.table(&key).iter().map(|s| s.time.as_secs()).collect();
+    assert_eq!(times, vec![5; 10]);
+    let other = ScoreKey {
+        no_guess: true,
+        ..key
+    };
+    assert!(loaded.table(&other).is_empty());
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn replayed_wins_are_not_recorded() {
+    let directory = std::env::temp_dir().join(format!("replayed_wins_{}", std::process::id()));
+    let options = BoardOptions {
+        map_size: (5, 5),
+        bomb_count: 3,
+        tile_size: TileSize::Fixed(10.),
+        seed: Some(2),
+        ..Default::default()
+    };
+    let game = Game::new(
+        (5, 5),
+        3,
+        2,
+        BombPlacement::OnCreation,
+        None,
+        Topology::Square,
+    );
+    // Reveals every safe tile once the replay started
+    let actions = game
+        .covered_tiles()
+        .filter(|c| !game.tile_map().is_bomb_at(*c))
+        .map(|coords: Coordinates| RecordedAction {
+            time: Duration::from_millis(1),
+            action: ReplayAction::Reveal,
+            coords,
+        })
+        .collect();
+    let path = directory.join("win.ron");
+    Replay {
+        game,
+        options: options.clone(),
+        actions,
+    }
+    .save(&path)
+    .unwrap();
+
+    let mut app = common::app(options.clone());
+    app.insert_resource(HighScores::load(&directory));
+    app.update();
+    app.world.send_event(PlayReplayEvent(path));
+    for _ in 0..5 {
+        thread::sleep(Duration::from_millis(2));
+        app.update();
+    }
+    let board = app.world.resource::<Board>();
+    assert_eq!(board.game.outcome(), Some(Outcome::Won));
+    let high_scores = app.world.resource::<HighScores>();
+    assert!(high_scores.table(&ScoreKey::from(&options)).is_empty());
+    std::fs::remove_dir_all(directory).unwrap();
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::{GamepadButtonType, KeyCode, MouseButton};
+use board_plugin::resources::{BoardAction, Binding, InputBindings};
+
+#[test]
+fn remapped_bindings_roundtrip() {
+    let mut bindings = InputBindings::default();
+    assert!(bindings.is_bound(BoardAction::Reveal, Binding::Mouse(MouseButton::Left)));
+    bindings.actions.insert(
+        BoardAction::Hint,
+        vec![Binding::Key(KeyCode::H), Binding::Gamepad(GamepadButtonType::Select)],
+    );
+
+    let loaded: InputBindings = ron::from_str(&ron::to_string(&bindings).unwrap()).unwrap();
+
+    assert_eq!(loaded.keys(BoardAction::Hint).collect::<Vec<_>>(), vec![KeyCode::H]);
+    assert_eq!(
+        loaded.gamepad_buttons(BoardAction::Hint).collect::<Vec<_>>(),
+        vec![GamepadButtonType::Select]
+    );
+    assert_eq!(
+        loaded.actions_of(Binding::Key(KeyCode::Space)).collect::<Vec<_>>(),
+        vec![BoardAction::Reveal]
+    );
+}
+
+#[test]
+fn missing_actions_keep_their_default_bindings() {
+    // Bindings saved before panning and zooming existed
+    let directory = std::env::temp_dir().join(format!("input_bindings_{}", std::process::id()));
+    let path = directory.join("input.ron");
+    std::fs::create_dir_all(&directory).unwrap();
+    std::fs::write(
+        &path,
+        "(actions: {Reveal: [Key(Return)], Flag: [Mouse(Right)], CursorUp: [Key(Up)]})",
+    )
+    .unwrap();
+
+    let loaded = InputBindings::load(&path).unwrap();
+    let defaults = InputBindings::default();
+    assert_eq!(loaded.bindings(BoardAction::Reveal), [Binding::Key(KeyCode::Return)]);
+    for action in [BoardAction::Pan, BoardAction::ZoomIn, BoardAction::Hint] {
+        assert!(!loaded.bindings(action).is_empty());
+        assert_eq!(loaded.bindings(action), defaults.bindings(action));
+    }
+    std::fs::remove_dir_all(directory).unwrap();
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use board_plugin::components::{Coordinates, Cursor};
+use board_plugin::resources::{Board, BoardOptions, BombPlacement, TileSize};
+
+/// Headless app running a 10x10 board, without window nor rendering
+fn app() -> App {
+    let mut app = common::app(BoardOptions {
+        map_size: (10, 10),
+        bomb_count: 10,
+        tile_size: TileSize::Fixed(10.),
+        seed: Some(1),
+        bomb_placement: BombPlacement::FirstClick { safe_square: true },
+        ..Default::default()
+    });
+    app.update();
+    app
+}
+
+fn send(app: &mut App, key: KeyCode, state: ButtonState) {
+    app.world.send_event(KeyboardInput {
+        scan_code: 0,
+        key_code: Some(key),
+        state,
+    });
+    app.update();
+}
+
+fn press(app: &mut App, key: KeyCode) {
+    send(app, key, ButtonState::Pressed);
+    send(app, key, ButtonState::Released);
+}
+
+fn cursor(app: &mut App) -> Coordinates {
+    app.world.query::<&Cursor>().single(&app.world).coordinates
+}
+
+#[test]
+fn keyboard_moves_the_cursor_and_plays() {
+    let mut app = app();
+    assert_eq!(cursor(&mut app), Coordinates { x: 5, y: 5 });
+
+    // The first input shows the cursor
+    press(&mut app, KeyCode::Right);
+    assert_eq!(cursor(&mut app), Coordinates { x: 5, y: 5 });
+    press(&mut app, KeyCode::Right);
+    assert_eq!(cursor(&mut app), Coordinates { x: 6, y: 5 });
+    press(&mut app, KeyCode::Up);
+    assert_eq!(cursor(&mut app), Coordinates { x: 6, y: 6 });
+    // Both arrows and WASD move it
+    press(&mut app, KeyCode::A);
+    press(&mut app, KeyCode::S);
+    assert_eq!(cursor(&mut app), Coordinates { x: 5, y: 5 });
+
+    let coords = Coordinates { x: 5, y: 5 };
+    press(&mut app, KeyCode::F);
+    assert!(app.world.resource::<Board>().game.is_flagged(coords));
+    press(&mut app, KeyCode::F);
+    assert!(!app.world.resource::<Board>().game.is_flagged(coords));
+    press(&mut app, KeyCode::Space);
+    let game = &app.world.resource::<Board>().game;
+    assert!(game.has_started() && !game.is_covered(coords));
+}
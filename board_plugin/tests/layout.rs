@@ -0,0 +1,114 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy::window::{WindowId, WindowResized};
+use board_plugin::components::{BoardCamera, Coordinates};
+use board_plugin::events::NewBoardEvent;
+use board_plugin::resources::{Board, BoardOptions, TileSize};
+
+/// Headless app running a 10x10 adaptive board in a 200x100 primary window, without rendering
+fn app() -> App {
+    let mut app = common::app(BoardOptions {
+        map_size: (10, 10),
+        bomb_count: 10,
+        tile_size: TileSize::Adaptive { min: 1., max: 100. },
+        tile_padding: 2.,
+        seed: Some(1),
+        ..Default::default()
+    });
+    app.world.resource_mut::<Windows>().add(Window::new(
+        WindowId::primary(),
+        &WindowDescriptor::default(),
+        200,
+        100,
+        1.,
+        None,
+        None,
+    ));
+    app.update();
+    app
+}
+
+fn resize(app: &mut App, width: u32, height: u32) {
+    app.world
+        .resource_mut::<Windows>()
+        .get_primary_mut()
+        .unwrap()
+        .update_actual_size_from_backend(width, height);
+    app.world.send_event(WindowResized {
+        id: WindowId::primary(),
+        width: width as f32,
+        height: height as f32,
+    });
+    app.update();
+}
+
+/// Translation and sprite size of the `coords` tile
+fn tile(app: &mut App, coords: Coordinates) -> (Vec3, Vec2) {
+    let (_, transform, sprite) = app
+        .world
+        .query::<(&Coordinates, &Transform, &Sprite)>()
+        .iter(&app.world)
+        .find(|(c, ..)| **c == coords)
+        .unwrap();
+    (transform.translation, sprite.custom_size.unwrap())
+}
+
+#[test]
+fn resizing_the_window_lays_the_board_out_again() {
+    let mut app = app();
+    let coords = Coordinates { x: 3, y: 4 };
+    assert_eq!(app.world.resource::<Board>().tile_size, 10.);
+    assert_eq!(tile(&mut app, coords), (Vec3::new(35., 45., 1.), Vec2::splat(8.)));
+    let covered = app.world.resource::<Board>().game.covered_tiles().count();
+
+    resize(&mut app, 400, 300);
+    let board = app.world.resource::<Board>();
+    assert_eq!(board.tile_size, 30.);
+    assert_eq!(board.bounds.size, Vec2::splat(300.));
+    assert_eq!(board.bounds.position, Vec2::splat(-150.));
+    assert_eq!(board.game.covered_tiles().count(), covered);
+    let cover = board.cover(coords).unwrap();
+    assert_eq!(tile(&mut app, coords), (Vec3::new(105., 135., 1.), Vec2::splat(28.)));
+    let cover_size = app.world.get::<Sprite>(cover).unwrap().custom_size;
+    assert_eq!(cover_size, Some(Vec2::splat(28.)));
+}
+
+/// Translation and scale of the `BoardCamera`
+fn camera(app: &mut App) -> (Vec3, f32) {
+    let (transform, projection) = app
+        .world
+        .query_filtered::<(&Transform, &OrthographicProjection), With<BoardCamera>>()
+        .single(&app.world);
+    (transform.translation, projection.scale)
+}
+
+#[test]
+fn only_new_boards_reset_the_camera() {
+    let mut app = app();
+    app.world.spawn((
+        Camera::default(),
+        GlobalTransform::default(),
+        Transform::default(),
+        OrthographicProjection::default(),
+        BoardCamera,
+    ));
+    app.update();
+    let moved = (Vec3::new(20., -10., 0.), 0.5);
+    let mut cameras = app
+        .world
+        .query_filtered::<(&mut Transform, &mut OrthographicProjection), With<BoardCamera>>();
+    let (mut transform, mut projection) = cameras.single_mut(&mut app.world);
+    transform.translation = moved.0;
+    projection.scale = moved.1;
+
+    resize(&mut app, 400, 300);
+    assert_eq!(camera(&mut app), moved);
+
+    app.world.send_event(NewBoardEvent(None));
+    app.update();
+    app.update();
+    let bounds = app.world.resource::<Board>().bounds;
+    let center = bounds.position + bounds.size / 2.;
+    assert_eq!(camera(&mut app), (center.extend(0.), 1.));
+}
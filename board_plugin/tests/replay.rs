@@ -0,0 +1,68 @@
+use board_plugin::components::Coordinates;
+use board_plugin::game::Game;
+use board_plugin::resources::{
+    BoardOptions, BombPlacement, RecordedAction, Replay, ReplayAction, Tile, Topology,
+};
+use std::time::Duration;
+
+fn action(secs: u64, action: ReplayAction, x: u16, y: u16) -> RecordedAction {
+    RecordedAction {
+        time: Duration::from_secs(secs),
+        action,
+        coords: Coordinates { x, y },
+    }
+}
+
+#[test]
+fn replays_rebuild_the_game_at_any_time() {
+    let game = Game::new(
+        (9, 9),
+        10,
+        3,
+        BombPlacement::FirstClick { safe_square: true },
+        None,
+        Topology::Square,
+    );
+    let mut played = game.clone();
+    played.reveal(Coordinates { x: 4, y: 4 });
+    // A bomb and a covered safe tile next to the uncovered region
+    let covered: Vec<Coordinates> = played.covered_tiles().collect();
+    let bomb = *covered
+        .iter()
+        .find(|c| played.tile_map().tile_at(**c) == Some(&Tile::Bomb))
+        .unwrap();
+    let safe = *covered
+        .iter()
+        .find(|c| !played.tile_map().tile_at(**c).unwrap().is_bomb())
+        .unwrap();
+    let replay = Replay {
+        game,
+        options: BoardOptions::beginner(),
+        actions: vec![
+            action(1, ReplayAction::Reveal, 4, 4),
+            action(3, ReplayAction::Flag, bomb.x, bomb.y),
+            action(5, ReplayAction::Reveal, safe.x, safe.y),
+        ],
+    };
+    assert_eq!(replay.duration(), Duration::from_secs(5));
+
+    let start = replay.game_at(Duration::ZERO);
+    assert!(!start.has_started());
+    let flagged = replay.game_at(Duration::from_secs(4));
+    assert!(flagged.is_flagged(bomb));
+    assert!(flagged.is_covered(safe));
+    let end = replay.game_at(replay.duration());
+    assert!(end.is_flagged(bomb));
+    assert!(!end.is_covered(safe));
+    assert_eq!(
+        flagged.covered_tiles().count(),
+        played.covered_tiles().count()
+    );
+
+    let loaded: Replay = ron::from_str(&ron::to_string(&replay).unwrap()).unwrap();
+    assert_eq!(loaded.actions, replay.actions);
+    assert_eq!(
+        loaded.game_at(replay.duration()).covered_tiles().count(),
+        end.covered_tiles().count()
+    );
+}
@@ -0,0 +1,19 @@
+use board_plugin::resources::{ron_file, InputBindings};
+use std::io;
+
+#[test]
+fn values_roundtrip_through_files() {
+    let directory = std::env::temp_dir().join(format!("ron_file_{}", std::process::id()));
+    let path = directory.join("data").join("input.ron");
+    let bindings = InputBindings::default();
+    ron_file::save(&bindings, &path).unwrap();
+    let loaded: InputBindings = ron_file::load(&path).unwrap();
+    assert_eq!(loaded.actions, bindings.actions);
+
+    let missing = ron_file::load::<InputBindings>(directory.join("missing.ron"));
+    assert_eq!(missing.unwrap_err().kind(), io::ErrorKind::NotFound);
+    std::fs::write(&path, "(").unwrap();
+    let invalid = ron_file::load::<InputBindings>(&path);
+    assert_eq!(invalid.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    std::fs::remove_dir_all(directory).unwrap();
+}
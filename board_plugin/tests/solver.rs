@@ -0,0 +1,175 @@
+use board_plugin::components::Coordinates;
+use board_plugin::game::{Game, Outcome};
+use board_plugin::resources::{BombPlacement, NoGuess, NoGuessFallback, TileMap, Topology};
+use board_plugin::probability::bomb_probabilities;
+use board_plugin::solver::{solves_from, Solver, SOLVE_NODE_BUDGET};
+
+#[test]
+fn one_two_one_pattern() {
+    let bombs = [Coordinates { x: 1, y: 2 }, Coordinates { x: 3, y: 2 }];
+    let mut tile_map = TileMap::empty(5, 3);
+    tile_map.set_bombs_at(&bombs);
+    let covered = (0..5).map(|x| Coordinates { x, y: 2 });
+
+    let deductions = Solver::new(&tile_map, covered, []).solve();
+    assert_eq!(deductions.bombs, bombs);
+    assert_eq!(
+        deductions.safe,
+        [0, 2, 4].map(|x| Coordinates { x, y: 2 }).to_vec()
+    );
+}
+
+#[test]
+fn deductions_are_sound() {
+    for seed in 0..30 {
+        let mut game = Game::new(
+            (16, 16),
+            40,
+            seed,
+            BombPlacement::FirstClick { safe_square: true },
+            None,
+            Topology::Square,
+        );
+        game.reveal(Coordinates { x: 8, y: 8 });
+        // Plays every deduction until the solver is stuck
+        loop {
+            let deductions = Solver::from_game(&game).solve();
+            if deductions.is_empty() {
+                break;
+            }
+            for coords in deductions.bombs {
+                assert!(game.tile_map().is_bomb_at(coords));
+                game.toggle_flag(coords);
+            }
+            for coords in deductions.safe {
+                assert!(!game.tile_map().is_bomb_at(coords));
+                assert_ne!(game.reveal(coords).outcome, Some(Outcome::Lost));
+            }
+        }
+    }
+}
+
+#[test]
+fn no_guess_boards_are_solvable() {
+    let no_guess = NoGuess {
+        max_attempts: 1,
+        fallback: NoGuessFallback::RemoveBomb,
+    };
+    for seed in 0..5 {
+        let start = Coordinates { x: 2, y: 9 };
+        let mut game = Game::new(
+            (20, 20),
+            60,
+            seed,
+            BombPlacement::FirstClick { safe_square: false },
+            Some(no_guess),
+            Topology::Square,
+        );
+        game.place_pending_bombs(start);
+        assert!(solves_from(game.tile_map(), start));
+    }
+}
+
+#[test]
+fn probabilities_weight_the_interior() {
+    // A single 1 in the corner: one bomb among its 3 covered neighbors
+    let mut tile_map = TileMap::empty(3, 3);
+    tile_map.set_bombs_at(&[Coordinates { x: 1, y: 1 }, Coordinates { x: 2, y: 2 }]);
+    let covered: Vec<_> = (0..3)
+        .flat_map(|y| (0..3).map(move |x| Coordinates { x, y }))
+        .filter(|c| c.x + c.y > 0)
+        .collect();
+    let solver = Solver::new(&tile_map, covered, []);
+
+    let probabilities = bomb_probabilities(&solver).unwrap();
+    assert_eq!(probabilities.len(), 8);
+    // 3 frontier solutions each leave 1 bomb for the 5 interior tiles
+    for (coords, probability) in probabilities {
+        let expected = if coords.x <= 1 && coords.y <= 1 { 1. / 3. } else { 1. / 5. };
+        assert!((probability - expected).abs() < 1e-9, "{coords}: {probability}");
+    }
+}
+
+#[test]
+fn probabilities_fall_back_to_the_density_without_budget() {
+    let mut tile_map = TileMap::empty(3, 3);
+    tile_map.set_bombs_at(&[Coordinates { x: 1, y: 1 }, Coordinates { x: 2, y: 2 }]);
+    let covered: Vec<_> = (0..3)
+        .flat_map(|y| (0..3).map(move |x| Coordinates { x, y }))
+        .filter(|c| c.x + c.y > 0)
+        .collect();
+    let solver = Solver::new(&tile_map, covered, []).with_node_budget(0);
+
+    let probabilities = bomb_probabilities(&solver).unwrap();
+    assert_eq!(probabilities.len(), 8);
+    // 2 bombs among the 8 covered tiles
+    for (coords, probability) in probabilities {
+        assert!((probability - 0.25).abs() < 1e-9, "{coords}: {probability}");
+    }
+}
+
+#[test]
+fn node_budget_stops_the_enumerations() {
+    let mut stopped = false;
+    for seed in 0..30 {
+        let mut game = Game::new(
+            (16, 16),
+            40,
+            seed,
+            BombPlacement::FirstClick { safe_square: true },
+            None,
+            Topology::Square,
+        );
+        game.reveal(Coordinates { x: 8, y: 8 });
+        let full = Solver::from_game(&game).solve();
+        let solver = Solver::from_game(&game).with_node_budget(0);
+        let limited = solver.solve();
+        assert_eq!(solver.nodes_left(), 0);
+        // Fewer deductions, all of them sound
+        assert!(limited.safe.iter().all(|c| full.safe.contains(c)));
+        assert!(limited.bombs.iter().all(|c| full.bombs.contains(c)));
+        stopped |= limited != full;
+    }
+    assert!(stopped);
+}
+
+#[test]
+fn large_no_guess_boards_stay_within_the_node_budget() {
+    let no_guess = NoGuess {
+        max_attempts: 10,
+        fallback: NoGuessFallback::KeepLast,
+    };
+    let game = Game::new(
+        (64, 64),
+        800,
+        1,
+        BombPlacement::OnCreation,
+        Some(no_guess),
+        Topology::Square,
+    );
+    assert_eq!(game.tile_map().bomb_count(), 800);
+    let opening = game.opening().unwrap();
+
+    // Plays the board as `solves_from` does, the rounds sharing one budget
+    let mut played = Game::from_tile_map(game.tile_map().clone(), 0);
+    played.reveal(opening);
+    let mut nodes = SOLVE_NODE_BUDGET;
+    while played.outcome().is_none() && nodes > 0 {
+        let solver = Solver::from_game(&played).with_node_budget(nodes);
+        let deductions = solver.solve();
+        nodes = solver.nodes_left();
+        if deductions.safe.is_empty() {
+            break;
+        }
+        for coords in deductions.bombs {
+            played.toggle_flag(coords);
+        }
+        for coords in deductions.safe {
+            played.reveal(coords);
+        }
+    }
+    assert_eq!(
+        played.outcome() == Some(Outcome::Won),
+        solves_from(game.tile_map(), opening)
+    );
+}
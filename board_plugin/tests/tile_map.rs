@@ -0,0 +1,52 @@
+use board_plugin::components::Coordinates;
+use board_plugin::resources::{Tile, TileMap, TileSet};
+
+#[test]
+fn same_seed_same_bombs() {
+    let mut a = TileMap::empty(20, 20);
+    a.set_bombs(40, 42);
+    let mut b = TileMap::empty(20, 20);
+    b.set_bombs(40, 42);
+
+    assert_eq!(a.tiles(), b.tiles());
+    let bombs = a.tiles().iter().filter(|tile| tile.is_bomb()).count();
+    assert_eq!(bombs, 40);
+}
+
+#[test]
+fn safe_tiles_never_hold_bombs() {
+    let start = Coordinates { x: 4, y: 4 };
+    let mut safe: Vec<_> = TileMap::empty(9, 9).safe_square_at(start).collect();
+    safe.push(start);
+    for seed in 0..50 {
+        let mut tile_map = TileMap::empty(9, 9);
+        tile_map.set_bombs_avoiding(72, seed, &safe);
+
+        assert_eq!(tile_map.bomb_count(), 72);
+        assert!(safe.iter().all(|c| !tile_map.is_bomb_at(*c)));
+        assert_eq!(tile_map.tile_at(start), Some(&Tile::Empty));
+    }
+}
+
+#[test]
+fn tile_sets_track_their_tiles() {
+    let mut set = TileSet::full(10, 7);
+    assert_eq!(set.len(), 70);
+    assert_eq!(set.indexes().last(), Some(69));
+    assert!(!set.contains(Coordinates { x: 10, y: 0 }));
+
+    let coords = Coordinates { x: 3, y: 6 };
+    assert!(set.remove(coords));
+    assert!(!set.remove(coords));
+    assert!(!set.contains(coords) && set.len() == 69);
+    assert!(set.insert(coords));
+    assert_eq!(set, TileSet::full(10, 7));
+
+    let mut set = TileSet::empty(10, 7);
+    set.insert(coords);
+    set.insert(Coordinates { x: 9, y: 0 });
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        vec![Coordinates { x: 9, y: 0 }, coords]
+    );
+}
@@ -0,0 +1,138 @@
+use bevy::prelude::Vec2;
+use board_plugin::components::Coordinates;
+use board_plugin::game::{Game, Outcome};
+use board_plugin::resources::{BoardOptions, BoardOptionsError, Tile, TileMap, Topology};
+
+fn neighbors(tile_map: &TileMap, coords: Coordinates) -> Vec<Coordinates> {
+    tile_map
+        .safe_square_at(coords)
+        .filter(|c| tile_map.tile_at(*c).is_some())
+        .collect()
+}
+
+#[test]
+fn hex_neighbors_are_mutual() {
+    let mut tile_map = TileMap::with_topology(7, 6, Topology::Hex);
+    for (coords, _) in tile_map.iter() {
+        let around = neighbors(&tile_map, coords);
+        let inside = (1..6).contains(&coords.x) && (1..5).contains(&coords.y);
+        assert!(around.len() <= 6 && (!inside || around.len() == 6));
+        for neighbor in around {
+            assert!(neighbors(&tile_map, neighbor).contains(&coords));
+        }
+    }
+
+    tile_map.set_bombs(20, 3);
+    for (coords, tile) in tile_map.iter() {
+        let bombs = neighbors(&tile_map, coords)
+            .into_iter()
+            .filter(|c| tile_map.is_bomb_at(*c))
+            .count();
+        assert_eq!(tile.is_bomb() || bombs == 0, tile_map.bomb_count_at(coords) == 0);
+        assert!(tile.is_bomb() || tile_map.bomb_count_at(coords) as usize == bombs);
+    }
+}
+
+#[test]
+fn hex_tiles_are_picked_inside_their_hexagon() {
+    let (dimensions, size) = ((7, 6), 10.);
+    let topology = Topology::Hex;
+    let board_size = topology.board_size(dimensions, size);
+    for y in 0..dimensions.1 {
+        for x in 0..dimensions.0 {
+            let coords = Coordinates { x, y };
+            let center = topology.tile_center(coords, size);
+            assert!(center.cmpgt(Vec2::ZERO).all() && center.cmplt(board_size).all());
+            // Close to the center, the top and bottom vertices and the slanted edges
+            for offset in [(0., 0.), (0., 0.55), (0., -0.55), (0.45, 0.25), (-0.45, -0.25)] {
+                let position = center + Vec2::new(offset.0, offset.1) * size;
+                assert_eq!(topology.tile_at(position, size, dimensions), Some(coords));
+            }
+        }
+    }
+    assert_eq!(topology.tile_at(Vec2::new(-1., 5.), size, dimensions), None);
+    let above = Vec2::new(board_size.x / 2., board_size.y + size);
+    assert_eq!(topology.tile_at(above, size, dimensions), None);
+}
+
+#[test]
+fn hex_safe_starts_keep_fewer_tiles_free() {
+    let options = BoardOptions {
+        map_size: (4, 4),
+        bomb_count: 9,
+        safe_start: true,
+        topology: Topology::Hex,
+        ..Default::default()
+    };
+    assert_eq!(options.validate(), Ok(()));
+    let square = BoardOptions {
+        topology: Topology::Square,
+        ..options
+    };
+    assert_eq!(square.validate(), Err(BoardOptionsError::BombCount { max: 7 }));
+}
+
+#[test]
+fn torus_numbers_count_across_edges() {
+    let corner = Coordinates { x: 0, y: 0 };
+    let mut torus = TileMap::with_topology(5, 5, Topology::Torus);
+    torus.set_bombs_at(&[corner]);
+    let mut square = TileMap::empty(5, 5);
+    square.set_bombs_at(&[corner]);
+
+    let mut around: Vec<_> = torus.safe_square_at(corner).collect();
+    around.sort();
+    let xs = [0, 1, 4];
+    let mut expected: Vec<_> = xs
+        .iter()
+        .flat_map(|x| xs.iter().map(move |y| Coordinates { x: *x, y: *y }))
+        .filter(|c| *c != corner)
+        .collect();
+    expected.sort();
+    assert_eq!(around, expected);
+    for coords in expected {
+        assert_eq!(torus.tile_at(coords), Some(&Tile::BombNeighbor(1)));
+        let wrapped = coords.x == 4 || coords.y == 4;
+        assert_eq!(square.tile_at(coords) == Some(&Tile::Empty), wrapped);
+    }
+    assert_eq!(torus.tile_at(Coordinates { x: 2, y: 2 }), Some(&Tile::Empty));
+    // Edge tiles of square maps have fewer neighbors
+    assert_eq!(square.safe_square_at(corner).count(), 3);
+}
+
+#[test]
+fn small_torus_neighbors_are_counted_once() {
+    let mut torus = TileMap::with_topology(2, 3, Topology::Torus);
+    torus.set_bombs_at(&[Coordinates { x: 0, y: 0 }, Coordinates { x: 1, y: 2 }]);
+    for (coords, tile) in torus.iter() {
+        let around: Vec<_> = torus.safe_square_at(coords).collect();
+        // The other tile of the line, and both tiles of the two other lines
+        assert_eq!(around.len(), 5);
+        assert!(!around.contains(&coords));
+        if !tile.is_bomb() {
+            assert_eq!(torus.bomb_count_at(coords), 2);
+        }
+    }
+}
+
+#[test]
+fn torus_regions_uncover_across_edges() {
+    // A column of bombs, the empty columns on both sides of the edges forming one region
+    let bombs: Vec<_> = (0..6).map(|y| Coordinates { x: 3, y }).collect();
+    let start = Coordinates { x: 0, y: 0 };
+    let across = Coordinates { x: 5, y: 3 };
+
+    let mut torus = TileMap::with_topology(6, 6, Topology::Torus);
+    torus.set_bombs_at(&bombs);
+    let mut game = Game::from_tile_map(torus, 0);
+    let reveal = game.reveal(start);
+    assert!(!game.is_covered(across));
+    assert_eq!(reveal.revealed.len(), 30);
+    assert_eq!(reveal.outcome, Some(Outcome::Won));
+
+    let mut square = TileMap::empty(6, 6);
+    square.set_bombs_at(&bombs);
+    let mut game = Game::from_tile_map(square, 0);
+    game.reveal(start);
+    assert!(game.is_covered(across));
+}
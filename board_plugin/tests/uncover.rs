@@ -0,0 +1,93 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use board_plugin::components::{Coordinates, Uncover, UncoverDelay};
+use board_plugin::events::TileTriggerEvent;
+use board_plugin::resources::{Board, BoardOptions, BombPlacement, Tile, TileSize};
+use std::time::Duration;
+
+const RING_DELAY: Duration = Duration::from_millis(50);
+
+/// Headless app running a 16x16 board, without window nor rendering
+fn app(reveal_ring_delay: Option<Duration>) -> App {
+    let mut app = common::app(BoardOptions {
+        map_size: (16, 16),
+        bomb_count: 20,
+        tile_size: TileSize::Fixed(10.),
+        seed: Some(4),
+        bomb_placement: BombPlacement::OnCreation,
+        reveal_ring_delay,
+        ..Default::default()
+    });
+    app.update();
+    app
+}
+
+/// An empty tile and the tiles its flood fill reveals, with their cover entity
+fn flood(app: &App) -> (Coordinates, HashMap<Coordinates, Entity>) {
+    let board = app.world.resource::<Board>();
+    let game = &board.game;
+    let (start, revealed) = game
+        .covered_tiles()
+        .filter(|c| game.tile_map().tile_at(*c) == Some(&Tile::Empty))
+        .map(|c| (c, game.clone().reveal(c).revealed))
+        .max_by_key(|(_, revealed)| revealed.len())
+        .unwrap();
+    let covers = revealed
+        .into_iter()
+        .map(|c| (c, board.cover(c).unwrap()))
+        .collect();
+    (start, covers)
+}
+
+#[test]
+fn flood_fills_are_uncovered_at_once() {
+    let mut app = app(None);
+    let (start, covers) = flood(&app);
+    assert!(covers.len() > 10);
+
+    app.world.send_event(TileTriggerEvent(start));
+    app.update();
+    // Every cover of the region is marked, then removed by a single uncover pass
+    for entity in covers.values() {
+        assert!(app.world.get::<Uncover>(*entity).is_some());
+        assert!(app.world.get::<UncoverDelay>(*entity).is_none());
+    }
+    app.update();
+    for entity in covers.values() {
+        assert!(app.world.get_entity(*entity).is_none());
+    }
+}
+
+#[test]
+fn rings_wait_longer_away_from_the_start() {
+    let mut app = app(Some(RING_DELAY));
+    let (start, covers) = flood(&app);
+
+    app.world.send_event(TileTriggerEvent(start));
+    app.update();
+    let delays: HashMap<Coordinates, Duration> = covers
+        .iter()
+        .filter_map(|(coords, entity)| {
+            let delay = app.world.get::<UncoverDelay>(*entity)?;
+            Some((*coords, delay.0.duration()))
+        })
+        .collect();
+    // Every revealed tile but the start waits for its ring
+    assert!(!delays.contains_key(&start));
+    assert_eq!(delays.len(), covers.len() - 1);
+    let tile_map = app.world.resource::<Board>().game.tile_map().clone();
+    let ring = |coords: &Coordinates| delays.get(coords).copied().unwrap_or(Duration::ZERO);
+    for (coords, delay) in delays.iter() {
+        assert!(*delay >= RING_DELAY);
+        // Each ring waits one delay more than the ring before it
+        let previous = tile_map
+            .safe_square_at(*coords)
+            .filter(|c| covers.contains_key(c))
+            .map(|c| ring(&c))
+            .min()
+            .unwrap();
+        assert_eq!(*delay, previous + RING_DELAY);
+    }
+}
@@ -0,0 +1,71 @@
+// autosave.rs
+use crate::DATA_DIRECTORY;
+use bevy::log;
+use bevy::prelude::*;
+use bevy::window::WindowCloseRequested;
+use board_plugin::events::SaveBoardEvent;
+use board_plugin::resources::{Board, GameStatus};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Saved game file name, in the data directory
+const FILE_NAME: &str = "autosave.ron";
+
+pub fn autosave_path() -> PathBuf {
+    PathBuf::from(DATA_DIRECTORY).join(FILE_NAME)
+}
+
+/// Saves the game in progress when the window is closed, and forgets finished games.
+///
+/// Must run before the update stage, the board plugin saves the game in the same frame
+pub fn autosave(
+    board: Option<Res<Board>>,
+    status: Res<GameStatus>,
+    mut close_requested_rdr: EventReader<WindowCloseRequested>,
+    mut save_board_ewr: EventWriter<SaveBoardEvent>,
+) {
+    if close_requested_rdr.iter().count() == 0 {
+        return;
+    }
+    if close_game(board.is_some(), *status, &autosave_path()) {
+        save_board_ewr.send(SaveBoardEvent(autosave_path()));
+    }
+}
+
+/// Is the game on the board to be saved at `path` when the window closes.
+///
+/// The saved game is only removed once a game is won or lost, closing from the menu keeps it
+fn close_game(on_board: bool, status: GameStatus, path: &Path) -> bool {
+    if on_board && status == GameStatus::Playing {
+        return true;
+    }
+    if status.is_over() && path.exists() {
+        log::info!("Removing the saved game");
+        if let Err(e) = fs::remove_file(path) {
+            log::error!("Failed to remove the saved game: {}", e);
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_game_is_kept_until_a_game_is_over() {
+        let directory = std::env::temp_dir().join(format!("autosave_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join(FILE_NAME);
+        fs::write(&path, "()").unwrap();
+
+        // Closing from the menu or the high scores, before resuming the saved game
+        assert!(!close_game(false, GameStatus::Ready, &path));
+        assert!(path.exists());
+        assert!(close_game(true, GameStatus::Playing, &path));
+        assert!(path.exists());
+        assert!(!close_game(false, GameStatus::Lost, &path));
+        assert!(!path.exists());
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
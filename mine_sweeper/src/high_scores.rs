@@ -0,0 +1,141 @@
+// high_scores.rs
+use crate::AppState;
+use bevy::log;
+use bevy::prelude::*;
+use board_plugin::resources::{BombPlacement, HighScores, ScoreKey, Topology};
+
+/// Entries listed for each table
+const LISTED_ENTRIES: usize = 5;
+
+/// Root node of the high score screen
+#[derive(Debug, Component)]
+pub struct HighScoreScreen;
+
+/// Opens the high score screen over the game with `H`, closes it with `H` or `Escape`
+pub fn high_scores_handler(mut state: ResMut<State<AppState>>, keys: Res<Input<KeyCode>>) {
+    match state.current() {
+        AppState::InGame if keys.just_pressed(KeyCode::H) => {
+            if let Err(e) = state.push(AppState::HighScores) {
+                log::warn!("Failed to open the high scores: {}", e);
+            }
+        }
+        AppState::HighScores
+            if keys.just_pressed(KeyCode::H) || keys.just_pressed(KeyCode::Escape) =>
+        {
+            if let Err(e) = state.pop() {
+                log::warn!("Failed to close the high scores: {}", e);
+            }
+        }
+        _ => (),
+    }
+}
+
+pub fn spawn_high_scores(
+    mut commands: Commands,
+    high_scores: Res<HighScores>,
+    asset_server: Res<AssetServer>,
+) {
+    let style = TextStyle {
+        font: asset_server.load("/fonts/pixeled.ttf"),
+        font_size: 12.,
+        color: Color::WHITE,
+    };
+    let mut sections = vec![TextSection::new(
+        "High scores\n\n",
+        TextStyle {
+            font_size: 20.,
+            ..style.clone()
+        },
+    )];
+    if high_scores.tables().is_empty() {
+        sections.push(TextSection::new("No game won yet\n", style.clone()));
+    }
+    for table in high_scores.tables() {
+        sections.push(TextSection::new(
+            format!("{}\n", key_label(&table.key)),
+            TextStyle {
+                color: Color::YELLOW,
+                ..style.clone()
+            },
+        ));
+        for (rank, entry) in table.entries.iter().take(LISTED_ENTRIES).enumerate() {
+            sections.push(TextSection::new(
+                format!(
+                    "{:>2}. {:>7.2}s  {}  {}\n",
+                    rank + 1,
+                    entry.time.as_secs_f32(),
+                    format_date(entry.date),
+                    entry.player
+                ),
+                style.clone(),
+            ));
+        }
+        sections.push(TextSection::new("\n", style.clone()));
+    }
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::rgba(0., 0., 0., 0.9).into(),
+            ..Default::default()
+        })
+        .insert(Name::new("HighScores"))
+        .insert(HighScoreScreen)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_sections(sections));
+        });
+}
+
+pub fn despawn_high_scores(mut commands: Commands, query: Query<Entity, With<HighScoreScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Short description of the board options of a table
+fn key_label(key: &ScoreKey) -> String {
+    let mut label = format!(
+        "{}x{}, {} bombs",
+        key.map_size.0, key.map_size.1, key.bomb_count
+    );
+    if key.safe_start {
+        label.push_str(", safe start");
+    }
+    if let BombPlacement::FirstClick { safe_square } = key.bomb_placement {
+        label.push_str(if safe_square {
+            ", safe first square"
+        } else {
+            ", safe first click"
+        });
+    }
+    if key.no_guess {
+        label.push_str(", no guess");
+    }
+    match key.topology {
+        Topology::Square => (),
+        Topology::Hex => label.push_str(", hex"),
+        Topology::Torus => label.push_str(", wrapping"),
+    }
+    label
+}
+
+/// Formats seconds since the Unix epoch as a `YYYY-MM-DD` UTC date
+fn format_date(timestamp: u64) -> String {
+    // Days to civil date, from Howard Hinnant's `civil_from_days`
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
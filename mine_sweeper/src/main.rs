@@ -1,14 +1,30 @@
 use bevy::prelude::*;
 use bevy::window::{WindowDescriptor, WindowPlugin};
-use board_plugin::resources::{BoardAssets, SpriteMaterial};
+use board_plugin::resources::{
+    BoardAssets, HighScores, HudStyle, InputBindings, PlayerName, ProbabilityOverlay,
+    SpriteMaterial,
+};
+use std::path::Path;
 
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 
-use board_plugin::{resources::BoardOptions, BoardPlugin};
+use board_plugin::events::{NewBoardEvent, RedoEvent, UndoEvent};
+use board_plugin::components::BoardCamera;
+use board_plugin::BoardPlugin;
 
 use bevy::log;
 
+mod autosave;
+mod high_scores;
+mod menu;
+mod replay;
+
+/// Directory of the high score, menu choice, input binding, saved game and replay files
+const DATA_DIRECTORY: &str = "data";
+/// Input bindings file, in the data directory
+const INPUT_FILE: &str = "input.ron";
+
 fn main() {
     let mut app = App::new();
     // Window setup
@@ -29,7 +45,30 @@ fn main() {
         .add_plugin(BoardPlugin {
             running_state: AppState::InGame,
         })
-        .add_system(state_handler);
+        .add_system(state_handler)
+        .add_system(overlay_handler)
+        .add_system(round_handler)
+        .add_system(high_scores::high_scores_handler)
+        .add_system(menu::menu_keys)
+        .add_system_to_stage(CoreStage::PreUpdate, autosave::autosave)
+        .add_system_set(
+            SystemSet::on_update(AppState::InGame).with_system(replay::replay_handler),
+        )
+        .add_system_set(SystemSet::on_enter(AppState::Menu).with_system(menu::spawn_menu))
+        .add_system_set(
+            SystemSet::on_update(AppState::Menu)
+                .with_system(menu::menu_buttons)
+                .with_system(menu::update_menu),
+        )
+        .add_system_set(SystemSet::on_exit(AppState::Menu).with_system(menu::despawn_menu))
+        .add_system_set(
+            SystemSet::on_enter(AppState::HighScores)
+                .with_system(high_scores::spawn_high_scores),
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::HighScores)
+                .with_system(high_scores::despawn_high_scores),
+        );
 
     #[cfg(feature = "debug")]
     // Debug hierarchy inspector
@@ -43,13 +82,15 @@ fn main() {
 
 fn camera_setup(mut commands: Commands) {
     // 2D orthographic camera
-    commands.spawn(Camera2dBundle::default());
+    commands.spawn((Camera2dBundle::default(), BoardCamera));
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum AppState {
     InGame,
     Out,
+    Menu,
+    HighScores,
 }
 
 fn state_handler(mut state: ResMut<State<AppState>>, keys: Res<Input<KeyCode>>) {
@@ -69,19 +110,66 @@ fn state_handler(mut state: ResMut<State<AppState>>, keys: Res<Input<KeyCode>>)
     }
 }
 
+fn round_handler(
+    keys: Res<Input<KeyCode>>,
+    mut new_board_ewr: EventWriter<NewBoardEvent>,
+    mut undo_ewr: EventWriter<UndoEvent>,
+    mut redo_ewr: EventWriter<RedoEvent>,
+) {
+    if keys.just_pressed(KeyCode::N) {
+        log::info!("new board");
+        new_board_ewr.send(NewBoardEvent(None));
+    }
+    // Only boards with undo enabled handle these
+    if keys.just_pressed(KeyCode::Z) {
+        undo_ewr.send(UndoEvent);
+    }
+    if keys.just_pressed(KeyCode::Y) {
+        redo_ewr.send(RedoEvent);
+    }
+}
+
+fn overlay_handler(mut overlay: ResMut<ProbabilityOverlay>, keys: Res<Input<KeyCode>>) {
+    if keys.just_pressed(KeyCode::P) {
+        overlay.enabled = !overlay.enabled;
+        log::info!("probability overlay: {}", overlay.enabled);
+    }
+}
+
+fn load_input_bindings(path: impl AsRef<Path>) -> InputBindings {
+    let path = path.as_ref();
+    if path.exists() {
+        return InputBindings::load(path).unwrap_or_else(|e| {
+            log::warn!("Invalid input bindings {}: {}", path.display(), e);
+            InputBindings::default()
+        });
+    }
+    let bindings = InputBindings::default();
+    if let Err(e) = bindings.save(path) {
+        log::warn!("Failed to save {}: {}", path.display(), e);
+    }
+    bindings
+}
+
 fn setup_board(
     mut commands: Commands,
     mut state: ResMut<State<AppState>>,
     asset_server: Res<AssetServer>,
 ) {
-    // Board plugin options
-    commands.insert_resource(BoardOptions {
-        map_size: (20, 20),
-        bomb_count: 40,
-        tile_padding: 1.,
-        safe_start: false,
-        ..Default::default()
-    });
+    // Board plugin options, from the last menu choice
+    let choice = menu::MenuChoice::load(DATA_DIRECTORY);
+    commands.insert_resource(choice.board_options().unwrap_or_else(|e| {
+        log::warn!("Invalid last menu choice: {}", e);
+        menu::MenuChoice::default().board_options().unwrap()
+    }));
+    commands.insert_resource(choice);
+    // High scores, recorded by the board plugin
+    commands.insert_resource(HighScores::load(DATA_DIRECTORY));
+    commands.insert_resource(PlayerName(
+        std::env::var("USER").unwrap_or_else(|_| "Player".to_string()),
+    ));
+    // Input bindings, written with the defaults on first launch so they can be edited
+    commands.insert_resource(load_input_bindings(Path::new(DATA_DIRECTORY).join(INPUT_FILE)));
     // Board assets
     commands.insert_resource(BoardAssets {
         label: "Default".to_string(),
@@ -120,9 +208,24 @@ fn setup_board(
             },
             ..Default::default()
         },
-        game_over_font_size: 72.
+        game_over_font_size: 72.,
+        probability_colors: BoardAssets::default_probability_colors(),
+        hud: HudStyle {
+            font: asset_server.load("/fonts/pixeled.ttf"),
+            font_size: 16.,
+            color: Color::WHITE,
+            position: UiRect {
+                left: Val::Px(10.),
+                top: Val::Px(10.),
+                ..Default::default()
+            },
+        },
+        cursor_material: SpriteMaterial {
+            color: Color::rgba(1., 1., 0., 0.4),
+            ..Default::default()
+        },
     });
-    // Plugin activation
+    // Main menu, starting the game
 
-    state.overwrite_replace(AppState::InGame).expect("666");
+    state.overwrite_replace(AppState::Menu).expect("666");
 }
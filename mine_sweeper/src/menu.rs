@@ -0,0 +1,430 @@
+// menu.rs
+use crate::autosave::autosave_path;
+use crate::{AppState, DATA_DIRECTORY};
+use bevy::log;
+use bevy::prelude::*;
+use board_plugin::events::LoadBoardEvent;
+use board_plugin::resources::{ron_file, BoardOptions, BoardOptionsError, RenderMode, Topology};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+/// Menu choice file name, in the data directory
+const FILE_NAME: &str = "menu.ron";
+/// Chunk size of the custom boards larger than `BoardOptions::MAX_MAP_SIZE`
+const CHUNK_SIZE: u16 = 32;
+
+const NORMAL_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+const HOVERED_BUTTON: Color = Color::rgb(0.35, 0.35, 0.35);
+const SELECTED_BUTTON: Color = Color::rgb(0.2, 0.45, 0.2);
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Difficulty {
+    #[default]
+    Beginner,
+    Intermediate,
+    Expert,
+    Custom,
+}
+
+/// Custom form values, which may be invalid until the game starts
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct CustomBoard {
+    pub width: u16,
+    pub height: u16,
+    pub mines: u16,
+    pub safe_start: bool,
+    /// Practice games, with undo and without high scores
+    #[serde(default)]
+    pub undo: bool,
+    #[serde(default)]
+    pub topology: Topology,
+}
+
+/// Last played difficulty and custom form, remembered between sessions. Must be used as a
+/// resource
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Resource)]
+pub struct MenuChoice {
+    pub difficulty: Difficulty,
+    pub custom: CustomBoard,
+}
+
+/// Custom form field
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub enum CustomField {
+    Width,
+    Height,
+    Mines,
+    SafeStart,
+    Undo,
+    Topology,
+}
+
+#[derive(Debug, Copy, Clone, Component)]
+pub enum MenuButton {
+    /// Resumes the saved game
+    Continue,
+    /// Starts a game with a preset, or the custom form
+    Start(Difficulty),
+    /// Adds to a custom form field
+    Step(CustomField, i32),
+    ToggleSafeStart,
+    ToggleUndo,
+    /// Picks the next tile topology
+    CycleTopology,
+}
+
+/// Root node of the menu
+#[derive(Debug, Component)]
+pub struct Menu;
+
+/// Custom form validation message
+#[derive(Debug, Component)]
+pub struct FormError;
+
+impl Default for CustomBoard {
+    fn default() -> Self {
+        Self {
+            width: 20,
+            height: 20,
+            mines: 40,
+            safe_start: false,
+            undo: false,
+            topology: Topology::Square,
+        }
+    }
+}
+
+impl MenuChoice {
+    /// Loads the last choice saved in `directory`, or the default one
+    pub fn load(directory: impl AsRef<Path>) -> Self {
+        let path = directory.as_ref().join(FILE_NAME);
+        match ron_file::load(&path) {
+            Ok(choice) => choice,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                log::error!("Failed to load {}: {}", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self, directory: impl AsRef<Path>) -> io::Result<()> {
+        ron_file::save(self, directory.as_ref().join(FILE_NAME))
+    }
+
+    /// Board options of the chosen difficulty
+    pub fn board_options(&self) -> Result<BoardOptions, BoardOptionsError> {
+        let options = match self.difficulty {
+            Difficulty::Beginner => BoardOptions::beginner(),
+            Difficulty::Intermediate => BoardOptions::intermediate(),
+            Difficulty::Expert => BoardOptions::expert(),
+            Difficulty::Custom => {
+                let custom = self.custom;
+                // Boards too large for tile entities are drawn in chunks
+                let large = custom.width.max(custom.height) > BoardOptions::MAX_MAP_SIZE;
+                let options = BoardOptions {
+                    map_size: (custom.width, custom.height),
+                    bomb_count: custom.mines,
+                    safe_start: custom.safe_start,
+                    undo: custom.undo,
+                    topology: custom.topology,
+                    render_mode: if large {
+                        RenderMode::Chunks { size: CHUNK_SIZE }
+                    } else {
+                        RenderMode::Sprites
+                    },
+                    ..Default::default()
+                };
+                options.validate()?;
+                options
+            }
+        };
+        Ok(BoardOptions {
+            tile_padding: 1.,
+            ..options
+        })
+    }
+
+    fn field_text(&self, field: CustomField) -> String {
+        match field {
+            CustomField::Width => format!("Width: {}", self.custom.width),
+            CustomField::Height => format!("Height: {}", self.custom.height),
+            CustomField::Mines => format!("Mines: {}", self.custom.mines),
+            CustomField::SafeStart => {
+                format!("Safe start: {}", if self.custom.safe_start { "on" } else { "off" })
+            }
+            CustomField::Undo => format!("Undo: {}", if self.custom.undo { "on" } else { "off" }),
+            CustomField::Topology => format!(
+                "Tiles: {}",
+                match self.custom.topology {
+                    Topology::Square => "squares",
+                    Topology::Hex => "hexagons",
+                    Topology::Torus => "wrapping squares",
+                }
+            ),
+        }
+    }
+}
+
+pub fn spawn_menu(mut commands: Commands, choice: Res<MenuChoice>, asset_server: Res<AssetServer>) {
+    let style = TextStyle {
+        font: asset_server.load("/fonts/pixeled.ttf"),
+        font_size: 14.,
+        color: Color::WHITE,
+    };
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .insert(Name::new("Menu"))
+        .insert(Menu)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Mine Sweeper",
+                TextStyle {
+                    font_size: 28.,
+                    ..style.clone()
+                },
+            ));
+            if autosave_path().exists() {
+                spawn_button(parent, "Continue", MenuButton::Continue, &style);
+            }
+            for (label, difficulty) in [
+                ("Beginner", Difficulty::Beginner),
+                ("Intermediate", Difficulty::Intermediate),
+                ("Expert", Difficulty::Expert),
+            ] {
+                spawn_button(parent, label, MenuButton::Start(difficulty), &style);
+            }
+            parent.spawn(TextBundle::from_section("Custom", style.clone()));
+            for (field, step) in [
+                (CustomField::Width, 1),
+                (CustomField::Height, 1),
+                (CustomField::Mines, 5),
+            ] {
+                spawn_row(parent, |row| {
+                    spawn_button(row, "-", MenuButton::Step(field, -step), &style);
+                    row.spawn(TextBundle::from_section(choice.field_text(field), style.clone()))
+                        .insert(field);
+                    spawn_button(row, "+", MenuButton::Step(field, step), &style);
+                });
+            }
+            spawn_row(parent, |row| {
+                row.spawn(TextBundle::from_section(
+                    choice.field_text(CustomField::SafeStart),
+                    style.clone(),
+                ))
+                .insert(CustomField::SafeStart);
+                spawn_button(row, "Toggle", MenuButton::ToggleSafeStart, &style);
+            });
+            spawn_row(parent, |row| {
+                row.spawn(TextBundle::from_section(
+                    choice.field_text(CustomField::Undo),
+                    style.clone(),
+                ))
+                .insert(CustomField::Undo);
+                spawn_button(row, "Toggle", MenuButton::ToggleUndo, &style);
+            });
+            spawn_row(parent, |row| {
+                row.spawn(TextBundle::from_section(
+                    choice.field_text(CustomField::Topology),
+                    style.clone(),
+                ))
+                .insert(CustomField::Topology);
+                spawn_button(row, "Change", MenuButton::CycleTopology, &style);
+            });
+            spawn_button(parent, "Play custom", MenuButton::Start(Difficulty::Custom), &style);
+            parent
+                .spawn(TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        color: Color::ORANGE_RED,
+                        ..style.clone()
+                    },
+                ))
+                .insert(FormError);
+            parent.spawn(TextBundle::from_section(
+                "Enter: play last choice",
+                TextStyle {
+                    font_size: 10.,
+                    ..style.clone()
+                },
+            ));
+        });
+}
+
+fn spawn_row(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(children);
+}
+
+fn spawn_button(parent: &mut ChildBuilder, label: &str, button: MenuButton, style: &TextStyle) {
+    parent
+        .spawn(ButtonBundle {
+            style: Style {
+                margin: UiRect::all(Val::Px(5.)),
+                padding: UiRect::new(Val::Px(10.), Val::Px(10.), Val::Px(5.), Val::Px(5.)),
+                ..Default::default()
+            },
+            background_color: NORMAL_BUTTON.into(),
+            ..Default::default()
+        })
+        .insert(button)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(label, style.clone()));
+        });
+}
+
+pub fn despawn_menu(mut commands: Commands, query: Query<Entity, With<Menu>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Applies the clicked menu buttons
+pub fn menu_buttons(
+    mut choice: ResMut<MenuChoice>,
+    mut board_options: ResMut<BoardOptions>,
+    mut state: ResMut<State<AppState>>,
+    mut load_board_ewr: EventWriter<LoadBoardEvent>,
+    buttons: Query<(&Interaction, &MenuButton), Changed<Interaction>>,
+    mut error_text: Query<&mut Text, With<FormError>>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        let error = match *button {
+            MenuButton::Continue => {
+                // The board plugin replaces the new board with the saved one
+                log::info!("continuing saved game");
+                if set_state(&mut state, AppState::InGame) {
+                    load_board_ewr.send(LoadBoardEvent(autosave_path()));
+                }
+                None
+            }
+            MenuButton::Start(difficulty) => {
+                start(difficulty, &mut choice, &mut board_options, &mut state).err()
+            }
+            MenuButton::Step(field, step) => {
+                let custom = &mut choice.custom;
+                let value = match field {
+                    CustomField::Width => &mut custom.width,
+                    CustomField::Height => &mut custom.height,
+                    CustomField::Mines => &mut custom.mines,
+                    CustomField::SafeStart | CustomField::Undo | CustomField::Topology => continue,
+                };
+                *value = (*value as i32 + step).clamp(0, u16::MAX as i32) as u16;
+                None
+            }
+            MenuButton::ToggleSafeStart => {
+                choice.custom.safe_start = !choice.custom.safe_start;
+                None
+            }
+            MenuButton::ToggleUndo => {
+                choice.custom.undo = !choice.custom.undo;
+                None
+            }
+            MenuButton::CycleTopology => {
+                choice.custom.topology = match choice.custom.topology {
+                    Topology::Square => Topology::Hex,
+                    Topology::Hex => Topology::Torus,
+                    Topology::Torus => Topology::Square,
+                };
+                None
+            }
+        };
+        for mut text in error_text.iter_mut() {
+            text.sections[0].value = error.map(|e| e.to_string()).unwrap_or_default();
+        }
+    }
+}
+
+/// Refreshes the custom form values and the button colors
+pub fn update_menu(
+    choice: Res<MenuChoice>,
+    mut fields: Query<(&mut Text, &CustomField)>,
+    mut buttons: Query<(&Interaction, &MenuButton, &mut BackgroundColor)>,
+) {
+    if choice.is_changed() {
+        for (mut text, field) in fields.iter_mut() {
+            text.sections[0].value = choice.field_text(*field);
+        }
+    }
+    for (interaction, button, mut color) in buttons.iter_mut() {
+        *color = match (interaction, button) {
+            (Interaction::Hovered | Interaction::Clicked, _) => HOVERED_BUTTON,
+            (_, MenuButton::Start(d)) if *d == choice.difficulty => SELECTED_BUTTON,
+            _ => NORMAL_BUTTON,
+        }
+        .into();
+    }
+}
+
+/// Plays the last choice with `Enter` from the menu, goes back to the menu with `Escape`
+pub fn menu_keys(
+    mut choice: ResMut<MenuChoice>,
+    mut board_options: ResMut<BoardOptions>,
+    mut state: ResMut<State<AppState>>,
+    keys: Res<Input<KeyCode>>,
+    mut error_text: Query<&mut Text, With<FormError>>,
+) {
+    match state.current() {
+        AppState::Menu if keys.just_pressed(KeyCode::Return) => {
+            let difficulty = choice.difficulty;
+            if let Err(e) = start(difficulty, &mut choice, &mut board_options, &mut state) {
+                for mut text in error_text.iter_mut() {
+                    text.sections[0].value = e.to_string();
+                }
+            }
+        }
+        AppState::InGame if keys.just_pressed(KeyCode::Escape) => {
+            log::info!("back to menu");
+            set_state(&mut state, AppState::Menu);
+        }
+        _ => (),
+    }
+}
+
+/// Validates and saves the choice, then starts the game
+fn start(
+    difficulty: Difficulty,
+    choice: &mut MenuChoice,
+    board_options: &mut BoardOptions,
+    state: &mut State<AppState>,
+) -> Result<(), BoardOptionsError> {
+    choice.difficulty = difficulty;
+    *board_options = choice.board_options()?;
+    if let Err(e) = choice.save(DATA_DIRECTORY) {
+        log::error!("Failed to save the menu choice: {}", e);
+    }
+    log::info!("starting {:?} game", difficulty);
+    set_state(state, AppState::InGame);
+    Ok(())
+}
+
+/// Queues a transition to `next`, returns `false` if another one was already queued this frame
+fn set_state(state: &mut State<AppState>, next: AppState) -> bool {
+    match state.set(next.clone()) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("Ignored the transition to {:?}: {}", next, e);
+            false
+        }
+    }
+}
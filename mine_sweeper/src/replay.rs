@@ -0,0 +1,69 @@
+// replay.rs
+use crate::DATA_DIRECTORY;
+use bevy::log;
+use bevy::prelude::*;
+use board_plugin::events::{PlayReplayEvent, ReplayControlEvent, SaveReplayEvent};
+use board_plugin::resources::ReplayPlayback;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Replay file name, in the data directory
+const FILE_NAME: &str = "replay.ron";
+
+/// Time skipped by a seek
+const SEEK_STEP: Duration = Duration::from_secs(5);
+
+const MIN_SPEED: f32 = 0.25;
+const MAX_SPEED: f32 = 16.;
+
+fn replay_path() -> PathBuf {
+    PathBuf::from(DATA_DIRECTORY).join(FILE_NAME)
+}
+
+/// Saves the replay of the current board with `F2`, plays or stops the saved one with `F3`.
+///
+/// During a replay `Space` pauses, `Up` and `Down` change the speed and `Left` and `Right` seek
+pub fn replay_handler(
+    keys: Res<Input<KeyCode>>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut save_replay_ewr: EventWriter<SaveReplayEvent>,
+    mut play_replay_ewr: EventWriter<PlayReplayEvent>,
+    mut replay_control_ewr: EventWriter<ReplayControlEvent>,
+) {
+    if keys.just_pressed(KeyCode::F2) {
+        save_replay_ewr.send(SaveReplayEvent(replay_path()));
+    }
+    let playback = match playback {
+        None => {
+            if keys.just_pressed(KeyCode::F3) {
+                log::info!("playing replay");
+                play_replay_ewr.send(PlayReplayEvent(replay_path()));
+            }
+            return;
+        }
+        Some(p) => p,
+    };
+    if keys.just_pressed(KeyCode::F3) {
+        replay_control_ewr.send(ReplayControlEvent::Stop);
+    }
+    if keys.just_pressed(KeyCode::Space) {
+        replay_control_ewr.send(ReplayControlEvent::TogglePause);
+    }
+    if keys.just_pressed(KeyCode::Up) {
+        let speed = (playback.speed * 2.).min(MAX_SPEED);
+        log::info!("replay speed: {}", speed);
+        replay_control_ewr.send(ReplayControlEvent::SetSpeed(speed));
+    }
+    if keys.just_pressed(KeyCode::Down) {
+        let speed = (playback.speed / 2.).max(MIN_SPEED);
+        log::info!("replay speed: {}", speed);
+        replay_control_ewr.send(ReplayControlEvent::SetSpeed(speed));
+    }
+    if keys.just_pressed(KeyCode::Left) {
+        let time = playback.time().saturating_sub(SEEK_STEP);
+        replay_control_ewr.send(ReplayControlEvent::Seek(time));
+    }
+    if keys.just_pressed(KeyCode::Right) {
+        replay_control_ewr.send(ReplayControlEvent::Seek(playback.time() + SEEK_STEP));
+    }
+}